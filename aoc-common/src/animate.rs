@@ -0,0 +1,334 @@
+//! Terminal animation for watching grid simulations.
+//!
+//! [`grid::print_grid`] dumps every frame one after another which scrolls
+//! away far too fast to follow on a real input. With `--animate` days hand
+//! [`frame`] a [`Screen`] instead and it's redrawn in place on stderr using
+//! ANSI escapes with a colour per cell:
+//!
+//! - `--fps` limits how fast frames go by (`0` for no limit).
+//! - `--viewport X,Y,WIDTH,HEIGHT` only draws part of the grid so big ones
+//!   still fit in the terminal. Without it the top left corner is drawn
+//!   sized to `$COLUMNS` x `$LINES` (when set).
+//!
+//! Only needs a terminal which understands 24 bit colour so works fine over
+//! SSH.
+use crate::render::Rgb;
+use color_eyre::eyre::Result;
+use grid::{Grid, Location};
+use std::collections::HashMap;
+use std::env;
+use std::fmt::{self, Display, Write as _};
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Options for animating in the terminal.
+#[derive(Clone, Debug, clap::Args)]
+#[group(skip)]
+pub struct AnimateArgs {
+    /// Animate simulations in the terminal (on stderr).
+    #[arg(long, default_value_t = false)]
+    pub animate: bool,
+
+    /// Frames per second when animating. 0 draws as fast as possible.
+    #[arg(long, default_value_t = 30)]
+    pub fps: u32,
+
+    /// Only draw this part of the grid when animating. Defaults to the top
+    /// left corner sized to $COLUMNS x $LINES when those are set.
+    #[arg(long, value_name = "X,Y,WIDTH,HEIGHT")]
+    pub viewport: Option<Viewport>,
+}
+
+impl Default for AnimateArgs {
+    fn default() -> Self {
+        Self {
+            animate: false,
+            fps: 30,
+            viewport: None,
+        }
+    }
+}
+
+/// The part of a grid to draw.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Viewport {
+    /// Leftmost column.
+    pub x: usize,
+    /// Top row.
+    pub y: usize,
+    /// How many columns.
+    pub width: usize,
+    /// How many rows.
+    pub height: usize,
+}
+
+impl Viewport {
+    /// Everything.
+    pub const ALL: Viewport = Viewport {
+        x: 0,
+        y: 0,
+        width: usize::MAX,
+        height: usize::MAX,
+    };
+
+    // The top left of the grid sized to fit the terminal (leaving a line for
+    // the title) if the shell says how big that is.
+    fn terminal() -> Self {
+        let size = |v| env::var(v).ok().and_then(|s| s.parse::<usize>().ok());
+        Viewport {
+            width: size("COLUMNS").unwrap_or(usize::MAX),
+            height: size("LINES").map_or(usize::MAX, |l| l.saturating_sub(1)),
+            ..Self::ALL
+        }
+    }
+}
+
+impl FromStr for Viewport {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let nums = s
+            .split(',')
+            .map(|n| n.trim().parse::<usize>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("{s}: {e}"))?;
+        let [x, y, width, height] = nums[..] else {
+            return Err(format!("{s}: expected X,Y,WIDTH,HEIGHT"));
+        };
+        Ok(Viewport {
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+}
+
+/// A grid of coloured characters ready to draw.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Screen {
+    width: usize,
+    height: usize,
+    cells: Vec<(char, Rgb)>,
+}
+
+impl Screen {
+    /// Each cell drawn as its [`Display`] character in the colour from
+    /// `colour`.
+    pub fn from_grid<T: Display>(grid: &Grid<T>, colour: impl Fn(&T) -> Rgb) -> Self {
+        let mut s = Self {
+            width: grid.width(),
+            height: grid.height(),
+            cells: vec![(' ', Rgb::BLACK); grid.width() * grid.height()],
+        };
+        for (l, v) in grid {
+            let ch = v.to_string().chars().next().unwrap_or(' ');
+            s.put(&l, ch, colour(v));
+        }
+        s
+    }
+
+    /// Change the cell at `l`. Anything outside the screen is ignored.
+    pub fn put(&mut self, l: &Location, ch: char, colour: Rgb) {
+        let x = usize::try_from(l.0).ok().filter(|x| *x < self.width);
+        let y = usize::try_from(l.1).ok().filter(|y| *y < self.height);
+        if let (Some(x), Some(y)) = (x, y) {
+            self.cells[y * self.width + x] = (ch, colour);
+        }
+    }
+
+    /// The part of the screen inside `view` with ANSI colour escapes. Every
+    /// line clears to the end so it can be drawn over an older frame.
+    #[must_use]
+    pub fn render(&self, view: Viewport) -> String {
+        let mut out = String::new();
+        let rows = self.height.min(view.y.saturating_add(view.height));
+        let cols = self.width.min(view.x.saturating_add(view.width));
+        for y in view.y..rows {
+            let mut last = None;
+            for x in view.x..cols {
+                let (ch, c) = self.cells[y * self.width + x];
+                // Only change colour when it does to keep frames small.
+                if last != Some(c) {
+                    let _ = write!(out, "\x1b[38;2;{};{};{}m", c.0, c.1, c.2);
+                    last = Some(c);
+                }
+                out.push(ch);
+            }
+            out.push_str("\x1b[0m\x1b[K\n");
+        }
+        out
+    }
+}
+
+impl fmt::Display for Screen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(Viewport::ALL))
+    }
+}
+
+struct State {
+    out: Box<dyn Write + Send>,
+    last: Option<Instant>,
+    // Frame number for each name.
+    counts: HashMap<String, usize>,
+}
+
+/// Draws frames in place at a fixed rate.
+pub struct Animator {
+    gap: Option<Duration>,
+    view: Viewport,
+    state: Mutex<State>,
+}
+
+impl Animator {
+    /// Draw to `out` at no more than `fps` frames a second (`0` for no
+    /// limit) showing only `view`.
+    pub fn new(out: impl Write + Send + 'static, fps: u32, view: Viewport) -> Self {
+        Self {
+            gap: (fps > 0).then(|| Duration::from_secs(1) / fps),
+            view,
+            state: Mutex::new(State {
+                out: Box::new(out),
+                last: None,
+                counts: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Draw `screen` over the last frame with `name` and its frame number
+    /// as the title. Waits first if the last frame was too recent.
+    ///
+    /// # Errors
+    /// If writing to the output fails.
+    ///
+    /// # Panics
+    /// If another thread panicked while drawing.
+    pub fn draw(&self, name: &str, screen: &Screen) -> Result<()> {
+        let mut st = self.state.lock().unwrap();
+        let n = st.counts.entry(name.to_string()).or_insert(0);
+        *n += 1;
+        let title = format!("{name} #{n}");
+
+        if let (Some(gap), Some(last)) = (self.gap, st.last) {
+            if let Some(wait) = gap.checked_sub(last.elapsed()) {
+                thread::sleep(wait);
+            }
+        }
+
+        // The very first frame clears the screen. After that just go back to
+        // the top and draw over it, clearing whatever's left below.
+        let start = if st.last.is_none() {
+            "\x1b[2J\x1b[H"
+        } else {
+            "\x1b[H"
+        };
+        let frame = format!("{start}{title}\x1b[K\n{}\x1b[J", screen.render(self.view));
+        st.out.write_all(frame.as_bytes())?;
+        st.out.flush()?;
+        st.last = Some(Instant::now());
+        Ok(())
+    }
+}
+
+static ANIMATOR: OnceLock<Animator> = OnceLock::new();
+
+/// Set up terminal animation from the command line. Does nothing unless
+/// `--animate` was given.
+pub fn init(args: &AnimateArgs) {
+    if args.animate {
+        let view = args.viewport.unwrap_or_else(Viewport::terminal);
+        // Only the first setup counts.
+        let _ = ANIMATOR.set(Animator::new(io::stderr(), args.fps, view));
+    }
+}
+
+/// Whether frames are being animated.
+#[must_use]
+pub fn enabled() -> bool {
+    ANIMATOR.get().is_some()
+}
+
+/// Draw the next frame for `name` if animating. `screen` is only called
+/// when it is.
+///
+/// # Errors
+/// If the terminal can't be written to.
+pub fn frame(name: &str, screen: impl FnOnce() -> Screen) -> Result<()> {
+    if let Some(a) = ANIMATOR.get() {
+        a.draw(name, &screen())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn viewport() {
+        let v = "1,2,30,40".parse::<Viewport>().unwrap();
+        assert_eq!((v.x, v.y, v.width, v.height), (1, 2, 30, 40));
+        assert!("1,2,3".parse::<Viewport>().is_err());
+        assert!("1,2,3,x".parse::<Viewport>().is_err());
+    }
+
+    fn screen() -> Screen {
+        let mut g = Grid::<u8>::new(3, 2);
+        g.add(&Location(1, 0), 7);
+        let mut s = Screen::from_grid(&g, |v| if *v == 0 { Rgb::GREY } else { Rgb::RED });
+        s.put(&Location(2, 1), '#', Rgb::WHITE);
+        s
+    }
+
+    #[test]
+    fn rendering() {
+        let s = screen();
+        let grey = "\x1b[38;2;63;63;63m";
+        let red = "\x1b[38;2;255;0;0m";
+        let white = "\x1b[38;2;255;255;255m";
+        let end = "\x1b[0m\x1b[K\n";
+        assert_eq!(
+            s.to_string(),
+            format!("{grey}0{red}7{grey}0{end}{grey}00{white}#{end}")
+        );
+        let crop = Viewport {
+            x: 1,
+            y: 1,
+            width: 5,
+            height: 5,
+        };
+        assert_eq!(s.render(crop), format!("{grey}0{white}#{end}"));
+    }
+
+    // Collects everything drawn so the test can look at it.
+    #[derive(Clone, Default)]
+    struct Sink(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Sink {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn redraws_in_place() {
+        let sink = Sink::default();
+        let a = Animator::new(sink.clone(), 0, Viewport::ALL);
+        a.draw("test", &screen()).unwrap();
+        a.draw("test", &screen()).unwrap();
+        let out = String::from_utf8(sink.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(out.matches("\x1b[2J").count(), 1);
+        assert!(out.starts_with("\x1b[2J\x1b[Htest #1\x1b[K\n"));
+        assert!(out.contains("\x1b[J\x1b[Htest #2\x1b[K\n"));
+    }
+}
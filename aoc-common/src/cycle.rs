@@ -0,0 +1,127 @@
+//! Cycle detection for simulate-until-it-repeats puzzles.
+//!
+//! Run a step function from a starting state until a state repeats. The
+//! sequence is then some prefix followed by a loop repeating forever which
+//! lets any step (even the billionth) be looked up without simulating it.
+//!
+//! States are compared by hashing them (or a key derived from them) so two
+//! different states which happen to score the same can't be mistaken for a
+//! repeat.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A sequence of states which ends up in a loop.
+#[derive(Clone, Debug)]
+pub struct Cycle<S> {
+    /// How many steps before the loop starts. The state after this many
+    /// steps is the first one which repeats.
+    pub prefix_len: usize,
+    /// Length of the loop.
+    pub period: usize,
+    // Every state from the start through one full loop.
+    states: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    /// Which of the recorded states is the same as the state after `n`
+    /// steps.
+    #[must_use]
+    pub fn index(&self, n: usize) -> usize {
+        if n < self.prefix_len {
+            n
+        } else {
+            self.prefix_len + (n - self.prefix_len) % self.period
+        }
+    }
+
+    /// The state after `n` steps (0 is the starting state).
+    #[must_use]
+    pub fn state(&self, n: usize) -> &S {
+        &self.states[self.index(n)]
+    }
+
+    /// Consume this and return the state after `n` steps.
+    #[must_use]
+    pub fn into_state(mut self, n: usize) -> S {
+        self.states.swap_remove(self.index(n))
+    }
+}
+
+/// Find the cycle reached by repeatedly applying `step` to `start`.
+///
+/// Returns None if nothing repeats within `limit` steps.
+pub fn find<S, F>(start: S, step: F, limit: usize) -> Option<Cycle<S>>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    find_by_key(start, step, S::clone, limit)
+}
+
+/// Like [`find`] but states are compared by `key`.
+///
+/// Useful when the state itself can't be hashed or only part of it matters.
+/// `key` must be unique per state (two states with the same key are treated
+/// as the same).
+pub fn find_by_key<S, K, F, G>(start: S, mut step: F, key: G, limit: usize) -> Option<Cycle<S>>
+where
+    K: Eq + Hash,
+    F: FnMut(&S) -> S,
+    G: Fn(&S) -> K,
+{
+    let mut seen = HashMap::from([(key(&start), 0)]);
+    let mut states = vec![start];
+    for n in 1..=limit {
+        let next = step(&states[n - 1]);
+        if let Some(first) = seen.insert(key(&next), n) {
+            return Some(Cycle {
+                prefix_len: first,
+                period: n - first,
+                states,
+            });
+        }
+        states.push(next);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next(x: u32) -> u32 {
+        (x * x + 1) % 255
+    }
+
+    #[test]
+    fn matches_simulation() {
+        let c = find(3, |x| next(*x), 1000).unwrap();
+        assert!(c.period > 0);
+        let mut x = 3;
+        for n in 0..2000 {
+            assert_eq!(*c.state(n), x, "step {n}");
+            x = next(x);
+        }
+    }
+
+    #[test]
+    fn pure_loop() {
+        let c = find(0, |x| (x + 1) % 5, 10).unwrap();
+        assert_eq!((c.prefix_len, c.period), (0, 5));
+        assert_eq!(c.index(1_000_000_000), 0);
+        assert_eq!(c.into_state(1_000_000_003), 3);
+    }
+
+    #[test]
+    fn prefix_and_limit() {
+        // 0 1 2 3 4 5 3 4 5 ...
+        let step = |x: &u32| if *x == 5 { 3 } else { x + 1 };
+        let c = find(0, step, 10).unwrap();
+        assert_eq!((c.prefix_len, c.period), (3, 3));
+        assert_eq!(*c.state(10), 4);
+        assert!(find(0, step, 5).is_none());
+        // Keys only need to tell states apart.
+        let c = find_by_key(0_u32, step, |x| x % 6, 10).unwrap();
+        assert_eq!(c.period, 3);
+    }
+}
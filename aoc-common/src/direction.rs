@@ -0,0 +1,262 @@
+//! Compass directions and stepping a [`Location`] around a grid.
+//!
+//! Grids are indexed with x increasing to the right (east) and y increasing
+//! downwards (south) so North is `y - 1`.
+use grid::{Grid, Location};
+use std::fmt;
+
+/// One of the four directions along the grid axes.
+///
+/// Variants are in clockwise order starting from North.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Direction {
+    /// Up (y - 1).
+    North,
+    /// Right (x + 1).
+    East,
+    /// Down (y + 1).
+    South,
+    /// Left (x - 1).
+    West,
+}
+
+/// One of the eight directions including the diagonals.
+///
+/// Variants are in clockwise order starting from North.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Compass {
+    /// Up (y - 1).
+    North,
+    /// Up and right.
+    NorthEast,
+    /// Right (x + 1).
+    East,
+    /// Down and right.
+    SouthEast,
+    /// Down (y + 1).
+    South,
+    /// Down and left.
+    SouthWest,
+    /// Left (x - 1).
+    West,
+    /// Up and left.
+    NorthWest,
+}
+
+/// Anything which can be turned into a single step on the grid.
+pub trait Heading: Copy {
+    /// The (x, y) change for one step this way.
+    fn delta(self) -> (isize, isize);
+}
+
+impl Direction {
+    /// Every direction in clockwise order starting from North.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// The direction after a 90 degree turn counter clockwise.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    /// The direction after a 90 degree turn clockwise.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    /// The direction pointing the other way.
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// Parse a single letter direction. Both `U`/`D`/`L`/`R` and
+    /// `N`/`S`/`E`/`W` are accepted.
+    #[must_use]
+    pub fn from_letter(c: char) -> Option<Self> {
+        match c {
+            'U' | 'N' => Some(Direction::North),
+            'R' | 'E' => Some(Direction::East),
+            'D' | 'S' => Some(Direction::South),
+            'L' | 'W' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    /// Parse the hex digit direction codes from day18's colors where
+    /// `0` is right, `1` down, `2` left and `3` up.
+    #[must_use]
+    pub fn from_hex_digit(c: char) -> Option<Self> {
+        match c {
+            '0' => Some(Direction::East),
+            '1' => Some(Direction::South),
+            '2' => Some(Direction::West),
+            '3' => Some(Direction::North),
+            _ => None,
+        }
+    }
+}
+
+impl Heading for Direction {
+    fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::East => (1, 0),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl Compass {
+    /// Every direction in clockwise order starting from North.
+    pub const ALL: [Compass; 8] = [
+        Compass::North,
+        Compass::NorthEast,
+        Compass::East,
+        Compass::SouthEast,
+        Compass::South,
+        Compass::SouthWest,
+        Compass::West,
+        Compass::NorthWest,
+    ];
+
+    /// The direction after a 45 degree turn counter clockwise.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    /// The direction after a 45 degree turn clockwise.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// The direction pointing the other way.
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl Heading for Compass {
+    fn delta(self) -> (isize, isize) {
+        match self {
+            Compass::North => (0, -1),
+            Compass::NorthEast => (1, -1),
+            Compass::East => (1, 0),
+            Compass::SouthEast => (1, 1),
+            Compass::South => (0, 1),
+            Compass::SouthWest => (-1, 1),
+            Compass::West => (-1, 0),
+            Compass::NorthWest => (-1, -1),
+        }
+    }
+}
+
+impl From<Direction> for Compass {
+    fn from(d: Direction) -> Self {
+        match d {
+            Direction::North => Compass::North,
+            Direction::East => Compass::East,
+            Direction::South => Compass::South,
+            Direction::West => Compass::West,
+        }
+    }
+}
+
+impl fmt::Display for Compass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Moving a [`Location`] around.
+pub trait Step {
+    /// The location one step away in `dir`.
+    #[must_use]
+    fn step<H: Heading>(&self, dir: H) -> Location;
+
+    /// The location `n` steps away in `dir`.
+    #[must_use]
+    fn step_by<H: Heading>(&self, dir: H, n: isize) -> Location;
+
+    /// The location one step away in `dir` if that's still inside `grid`.
+    #[must_use]
+    fn step_within<H: Heading, T>(&self, dir: H, grid: &Grid<T>) -> Option<Location>;
+}
+
+impl Step for Location {
+    fn step<H: Heading>(&self, dir: H) -> Location {
+        self.step_by(dir, 1)
+    }
+
+    fn step_by<H: Heading>(&self, dir: H, n: isize) -> Location {
+        let (dx, dy) = dir.delta();
+        Location(self.0 + dx * n, self.1 + dy * n)
+    }
+
+    fn step_within<H: Heading, T>(&self, dir: H, grid: &Grid<T>) -> Option<Location> {
+        let l = self.step(dir);
+        let inside = usize::try_from(l.0).is_ok_and(|x| x < grid.width())
+            && usize::try_from(l.1).is_ok_and(|y| y < grid.height());
+        inside.then_some(l)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        use Direction::{East, North, South, West};
+        assert_eq!(North.turn_right(), East);
+        assert_eq!(North.turn_left(), West);
+        assert_eq!(West.turn_right(), North);
+        assert_eq!(East.opposite(), West);
+        for d in Direction::ALL {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.opposite().opposite(), d);
+        }
+        assert_eq!(Compass::North.turn_left(), Compass::NorthWest);
+        assert_eq!(Compass::SouthEast.opposite(), Compass::NorthWest);
+        assert_eq!(Compass::from(South), Compass::South);
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!(Direction::from_letter('U'), Some(Direction::North));
+        assert_eq!(Direction::from_letter('W'), Some(Direction::West));
+        assert_eq!(Direction::from_letter('x'), None);
+        assert_eq!(Direction::from_hex_digit('0'), Some(Direction::East));
+        assert_eq!(Direction::from_hex_digit('3'), Some(Direction::North));
+        assert_eq!(Direction::from_hex_digit('4'), None);
+    }
+
+    #[test]
+    fn stepping() {
+        let g = Grid::<u8>::new(3, 2);
+        let l = Location(0, 0);
+        assert_eq!(l.step(Direction::South), Location(0, 1));
+        assert_eq!(l.step(Compass::NorthWest), Location(-1, -1));
+        assert_eq!(l.step_by(Direction::East, 5), Location(5, 0));
+        assert_eq!(l.step_within(Direction::North, &g), None);
+        assert_eq!(l.step_within(Direction::East, &g), Some(Location(1, 0)));
+        assert_eq!(Location(2, 1).step_within(Direction::East, &g), None);
+        assert_eq!(Location(2, 1).step_within(Direction::South, &g), None);
+    }
+}
@@ -0,0 +1,268 @@
+//! Lattice polygon geometry over [`Location`] vertices.
+//!
+//! Puzzles which trace a loop around a grid (pipes, dig plans) usually want
+//! to know how many grid points the loop encloses. Flood filling works but
+//! gets slow (or impossible) once the loop is huge. With the vertices in hand
+//! it's exact arithmetic instead:
+//!
+//! - The shoelace formula gives the area.
+//! - Each edge from `a` to `b` passes through `gcd(|dx|, |dy|)` lattice points
+//!   which gives the boundary count.
+//! - Pick's theorem (`A = I + B/2 - 1`) then gives the interior count.
+//!
+//! Everything is computed in i128 so nothing overflows even with coordinates
+//! in the millions.
+use color_eyre::eyre::{eyre, Result};
+use grid::Location;
+
+/// Which way a polygon's vertices go round.
+///
+/// Grids have y increasing downwards so this is as seen on screen.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Orientation {
+    /// Like a clock (North then East).
+    Clockwise,
+    /// The other way (North then West).
+    CounterClockwise,
+}
+
+/// Where a point is relative to a polygon.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Position {
+    /// Strictly inside.
+    Inside,
+    /// On one of the edges (or a vertex).
+    Boundary,
+    /// Strictly outside.
+    Outside,
+}
+
+/// A simple polygon with lattice point vertices.
+///
+/// The closing edge from the last vertex back to the first is implied.
+/// Edges aren't checked for crossing each other so the vertices must trace
+/// a simple loop for the counts to mean anything.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Polygon {
+    vertices: Vec<Location>,
+}
+
+impl Polygon {
+    /// Build a polygon from its vertices in order.
+    ///
+    /// A final vertex repeating the first (a path which returns to its start)
+    /// is dropped.
+    ///
+    /// # Errors
+    /// If there are fewer than `3` vertices or they enclose no area.
+    pub fn new(mut vertices: Vec<Location>) -> Result<Self> {
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+        if vertices.len() < 3 {
+            return Err(eyre!(
+                "polygon needs at least 3 vertices, got {}",
+                vertices.len()
+            ));
+        }
+        let p = Self { vertices };
+        if p.double_area() == 0 {
+            return Err(eyre!("polygon vertices enclose no area"));
+        }
+        Ok(p)
+    }
+
+    /// Build a polygon from a path which must end where it began.
+    ///
+    /// # Errors
+    /// If the path doesn't return to its start or [`Polygon::new`] fails.
+    pub fn from_path(path: Vec<Location>) -> Result<Self> {
+        match (path.first(), path.last()) {
+            (Some(first), Some(last)) if path.len() > 1 && first == last => Self::new(path),
+            (Some(first), Some(last)) => Err(eyre!(
+                "path starts at {first} but ends at {last} so isn't closed"
+            )),
+            _ => Err(eyre!("empty path")),
+        }
+    }
+
+    /// The vertices in order (without repeating the first).
+    #[must_use]
+    pub fn vertices(&self) -> &[Location] {
+        &self.vertices
+    }
+
+    // Each edge as a pair of wide points.
+    fn edges(&self) -> impl Iterator<Item = ((i128, i128), (i128, i128))> + '_ {
+        let n = self.vertices.len();
+        (0..n).map(move |i| (wide(&self.vertices[i]), wide(&self.vertices[(i + 1) % n])))
+    }
+
+    /// Twice the signed area from the shoelace formula. Positive for
+    /// clockwise polygons. Always exact (the area itself may end in a half).
+    #[must_use]
+    pub fn signed_double_area(&self) -> i128 {
+        self.edges().map(|(a, b)| a.0 * b.1 - a.1 * b.0).sum()
+    }
+
+    /// Twice the area.
+    #[must_use]
+    pub fn double_area(&self) -> i128 {
+        self.signed_double_area().abs()
+    }
+
+    /// The area rounded down to a whole number. Exact for any polygon whose
+    /// edges all run along the grid axes.
+    #[must_use]
+    pub fn area(&self) -> i128 {
+        self.double_area() / 2
+    }
+
+    /// Which way the vertices go round.
+    #[must_use]
+    pub fn orientation(&self) -> Orientation {
+        if self.signed_double_area() > 0 {
+            Orientation::Clockwise
+        } else {
+            Orientation::CounterClockwise
+        }
+    }
+
+    /// The same polygon with its vertices going round the given way.
+    #[must_use]
+    pub fn oriented(mut self, o: Orientation) -> Self {
+        if self.orientation() != o {
+            self.vertices.reverse();
+        }
+        self
+    }
+
+    /// How many lattice points lie on the edges.
+    #[must_use]
+    pub fn boundary_points(&self) -> i128 {
+        self.edges()
+            .map(|(a, b)| gcd((b.0 - a.0).abs(), (b.1 - a.1).abs()))
+            .sum()
+    }
+
+    /// How many lattice points are strictly inside (from Pick's theorem).
+    #[must_use]
+    pub fn interior_points(&self) -> i128 {
+        (self.double_area() - self.boundary_points() + 2) / 2
+    }
+
+    /// Where `p` is relative to the polygon.
+    ///
+    /// Casts a ray from `p` towards +x and counts the edges it crosses. Each
+    /// edge only counts if it spans `p`'s y half open (one end strictly above
+    /// and the other at or below) so a ray passing exactly through a vertex
+    /// is never counted twice.
+    #[must_use]
+    pub fn locate(&self, p: &Location) -> Position {
+        let p = wide(p);
+        let mut inside = false;
+        for (a, b) in self.edges() {
+            let cross = (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0);
+            if cross == 0
+                && p.0 >= a.0.min(b.0)
+                && p.0 <= a.0.max(b.0)
+                && p.1 >= a.1.min(b.1)
+                && p.1 <= a.1.max(b.1)
+            {
+                return Position::Boundary;
+            }
+            if (a.1 > p.1) != (b.1 > p.1) {
+                // The edge crosses p's row. It's to the right of p when p is
+                // on the left side of it (relative to the edge going up or
+                // down) which is the sign of cross.
+                if (cross > 0) == (b.1 > a.1) {
+                    inside = !inside;
+                }
+            }
+        }
+        if inside {
+            Position::Inside
+        } else {
+            Position::Outside
+        }
+    }
+
+    /// Whether `p` is strictly inside.
+    #[must_use]
+    pub fn contains(&self, p: &Location) -> bool {
+        self.locate(p) == Position::Inside
+    }
+}
+
+#[allow(clippy::cast_lossless)]
+fn wide(l: &Location) -> (i128, i128) {
+    (l.0 as i128, l.1 as i128)
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poly(v: &[(isize, isize)]) -> Polygon {
+        Polygon::new(v.iter().map(|&(x, y)| Location(x, y)).collect()).unwrap()
+    }
+
+    #[test]
+    fn square() {
+        // 0,0 -> 4,0 -> 4,3 -> 0,3 is clockwise on screen.
+        let p = poly(&[(0, 0), (4, 0), (4, 3), (0, 3), (0, 0)]);
+        assert_eq!(p.vertices().len(), 4);
+        assert_eq!(p.area(), 12);
+        assert_eq!(p.orientation(), Orientation::Clockwise);
+        assert_eq!(p.boundary_points(), 14);
+        assert_eq!(p.interior_points(), 6);
+        let p = p.oriented(Orientation::CounterClockwise);
+        assert_eq!(p.signed_double_area(), -24);
+        assert_eq!(p.interior_points(), 6);
+    }
+
+    #[test]
+    fn diagonal_edges() {
+        // Half unit area and edges with lattice points part way along.
+        let p = poly(&[(0, 0), (4, 2), (0, 3)]);
+        assert_eq!(p.double_area(), 12);
+        assert_eq!(p.boundary_points(), 2 + 1 + 3);
+        assert_eq!(p.interior_points(), 4);
+        let inside = (-1..6)
+            .flat_map(|x| (-1..5).map(move |y| Location(x, y)))
+            .filter(|l| p.contains(l))
+            .count();
+        assert_eq!(inside, 4);
+    }
+
+    #[test]
+    fn locate() {
+        // An L shape so the ray crosses vertices and runs along edges.
+        let p = poly(&[(0, 0), (2, 0), (2, 2), (4, 2), (4, 4), (0, 4)]);
+        assert_eq!(p.locate(&Location(1, 1)), Position::Inside);
+        assert_eq!(p.locate(&Location(3, 3)), Position::Inside);
+        assert_eq!(p.locate(&Location(1, 2)), Position::Inside);
+        assert_eq!(p.locate(&Location(3, 1)), Position::Outside);
+        assert_eq!(p.locate(&Location(-1, 2)), Position::Outside);
+        assert_eq!(p.locate(&Location(3, 2)), Position::Boundary);
+        assert_eq!(p.locate(&Location(0, 0)), Position::Boundary);
+        assert_eq!(p.interior_points(), 5);
+    }
+
+    #[test]
+    fn invalid() {
+        let l = |v: &[(isize, isize)]| v.iter().map(|&(x, y)| Location(x, y)).collect();
+        assert!(Polygon::new(l(&[(0, 0), (1, 0), (0, 0)])).is_err());
+        assert!(Polygon::new(l(&[(0, 0), (1, 0), (2, 0)])).is_err());
+        assert!(Polygon::from_path(l(&[(0, 0), (1, 0), (1, 1)])).is_err());
+        assert!(Polygon::from_path(l(&[(0, 0), (1, 0), (1, 1), (0, 0)])).is_ok());
+    }
+}
@@ -0,0 +1,197 @@
+//! Command line arguments and puzzle input loading.
+use crate::output::Format;
+use crate::trace::TraceArgs;
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use std::env;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// Environment variable pointing at a directory of puzzle inputs.
+///
+/// When set relative filenames resolve against `$AOC_INPUT_DIR/dayN` instead
+/// of the day's crate directory. This lets the private inputs live outside
+/// the repo.
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+
+/// Arguments every day accepts. Days flatten this into their own clap struct
+/// and add any extra options they need alongside it.
+#[derive(Clone, Debug, clap::Args)]
+#[group(skip)]
+pub struct Args {
+    /// Input file to load. Relative paths resolve against the day directory
+    /// (or $`AOC_INPUT_DIR`/dayN when set). Use - to read from stdin.
+    #[arg(long, default_value_t = String::from("input.txt"))]
+    pub filename: String,
+
+    /// Logging options.
+    #[command(flatten)]
+    pub trace: TraceArgs,
+
+    /// How to print the answers.
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+impl Args {
+    /// Resolve the input for the day crate living in `manifest_dir`.
+    ///
+    /// Callers pass `env!("CARGO_MANIFEST_DIR")` so this resolves against
+    /// the day crate and not this one.
+    #[must_use]
+    pub fn input(&self, manifest_dir: &str) -> Input {
+        Input::new(&self.filename, manifest_dir)
+    }
+}
+
+/// Where a day's puzzle input comes from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Input {
+    /// Read everything from stdin.
+    Stdin,
+    /// Read from the given file.
+    File(PathBuf),
+}
+
+impl Input {
+    /// Resolve `filename` for the day crate living in `manifest_dir`.
+    ///
+    /// - `-` means stdin.
+    /// - Absolute paths are used as is.
+    /// - Relative paths resolve against `$AOC_INPUT_DIR/dayN` if that's set
+    ///   and otherwise against `manifest_dir` itself.
+    #[must_use]
+    pub fn new(filename: &str, manifest_dir: &str) -> Self {
+        if filename == "-" {
+            return Input::Stdin;
+        }
+        let path = Path::new(filename);
+        if path.is_absolute() {
+            return Input::File(path.to_path_buf());
+        }
+
+        let manifest = Path::new(manifest_dir);
+        let base = match env::var_os(INPUT_DIR_ENV) {
+            Some(dir) if !dir.is_empty() => {
+                let dir = PathBuf::from(dir);
+                match manifest.file_name() {
+                    Some(day) => dir.join(day),
+                    None => dir,
+                }
+            }
+            _ => manifest.to_path_buf(),
+        };
+        Input::File(base.join(path))
+    }
+
+    /// Read the raw bytes of the input.
+    ///
+    /// # Errors
+    /// If the file (or stdin) can't be read.
+    pub fn bytes(&self) -> Result<Vec<u8>> {
+        let mut buf = vec![];
+        match self {
+            Input::Stdin => {
+                io::stdin()
+                    .lock()
+                    .read_to_end(&mut buf)
+                    .wrap_err("can't read stdin")?;
+            }
+            Input::File(path) => {
+                buf = fs::read(path).wrap_err_with(|| format!("can't read {}", path.display()))?;
+            }
+        }
+        Ok(buf)
+    }
+
+    /// Read the input a piece at a time instead of all at once. For inputs
+    /// too big to want in memory.
+    ///
+    /// # Errors
+    /// If the file can't be opened.
+    pub fn reader(&self) -> Result<Box<dyn BufRead>> {
+        Ok(match self {
+            Input::Stdin => Box::new(io::stdin().lock()),
+            Input::File(path) => Box::new(BufReader::new(
+                File::open(path).wrap_err_with(|| format!("can't read {}", path.display()))?,
+            )),
+        })
+    }
+
+    /// Read the input as text.
+    ///
+    /// Unlike `BufRead::lines().map_while(Result::ok)` this never silently
+    /// truncates. Invalid UTF-8 is an error naming the line it's on.
+    ///
+    /// # Errors
+    /// If the input can't be read or isn't valid UTF-8.
+    pub fn string(&self) -> Result<String> {
+        String::from_utf8(self.bytes()?).map_err(|e| {
+            let bad = e.utf8_error().valid_up_to();
+            let line = e.as_bytes()[..bad].split(|b| *b == b'\n').count();
+            eyre!("{self}: invalid UTF-8 on line {line}")
+        })
+    }
+
+    /// Read the input as a list of lines with line endings removed.
+    ///
+    /// # Errors
+    /// If the input can't be read or isn't valid UTF-8.
+    pub fn lines(&self) -> Result<Vec<String>> {
+        Ok(self.string()?.lines().map(String::from).collect())
+    }
+
+    /// Read the input as groups of lines separated by blank lines.
+    ///
+    /// # Errors
+    /// If the input can't be read or isn't valid UTF-8.
+    pub fn blocks(&self) -> Result<Vec<Vec<String>>> {
+        Ok(blocks(&self.string()?)
+            .into_iter()
+            .map(|b| b.into_iter().map(String::from).collect())
+            .collect())
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Input::Stdin => write!(f, "<stdin>"),
+            Input::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Split text into groups of lines separated by one or more blank lines.
+///
+/// Leading/trailing blank lines never produce empty groups.
+#[must_use]
+pub fn blocks(text: &str) -> Vec<Vec<&str>> {
+    numbered_blocks(text)
+        .into_iter()
+        .map(|b| b.into_iter().map(|(_, l)| l).collect())
+        .collect()
+}
+
+/// Same as [`blocks`] but each line comes with its line number (starting at
+/// 1) in the original text for error reporting.
+#[must_use]
+pub fn numbered_blocks(text: &str) -> Vec<Vec<(usize, &str)>> {
+    let mut ret = vec![];
+    let mut cur = vec![];
+    for (line_num, line) in text.lines().enumerate() {
+        if line.is_empty() {
+            if !cur.is_empty() {
+                ret.push(cur);
+                cur = vec![];
+            }
+            continue;
+        }
+        cur.push((line_num + 1, line));
+    }
+    if !cur.is_empty() {
+        ret.push(cur);
+    }
+    ret
+}
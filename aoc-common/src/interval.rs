@@ -0,0 +1,394 @@
+//! Interval arithmetic for puzzles which push whole ranges of numbers
+//! around instead of one value at a time.
+//!
+//! Everything here is half-open (`start..end`) to match [`std::ops::Range`]
+//! so splitting never needs a `+ 1` anywhere: splitting at `x` gives
+//! `start..x` and `x..end`.
+use std::fmt;
+use std::ops::Range;
+
+/// The half-open range of integers `start..end`. Empty if `start >= end`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Interval {
+    /// First value included.
+    pub start: i64,
+    /// First value after the interval.
+    pub end: i64,
+}
+
+impl Interval {
+    /// The interval `start..end`.
+    #[must_use]
+    pub fn new(start: i64, end: i64) -> Self {
+        Self { start, end }
+    }
+
+    /// The interval holding `len` values from `start`.
+    #[must_use]
+    pub fn with_len(start: i64, len: i64) -> Self {
+        Self::new(start, start + len)
+    }
+
+    /// Whether there's nothing in this interval.
+    #[must_use]
+    pub fn is_empty(self) -> bool {
+        self.start >= self.end
+    }
+
+    /// How many values are in the interval.
+    #[must_use]
+    pub fn len(self) -> u64 {
+        if self.is_empty() {
+            0
+        } else {
+            self.end.abs_diff(self.start)
+        }
+    }
+
+    /// Whether `x` is in the interval.
+    #[must_use]
+    pub fn contains(self, x: i64) -> bool {
+        self.start <= x && x < self.end
+    }
+
+    /// The values in both intervals if there are any.
+    #[must_use]
+    pub fn intersect(self, other: Self) -> Option<Self> {
+        let i = Self::new(self.start.max(other.start), self.end.min(other.end));
+        (!i.is_empty()).then_some(i)
+    }
+
+    /// Split into the values below `x` and the values from `x` up. Either
+    /// half may be empty.
+    #[must_use]
+    pub fn split_at(self, x: i64) -> (Self, Self) {
+        let x = x.clamp(self.start, self.end.max(self.start));
+        (Self::new(self.start, x), Self::new(x, self.end))
+    }
+
+    /// The interval moved by `offset`.
+    #[must_use]
+    pub fn shift(self, offset: i64) -> Self {
+        Self::new(self.start + offset, self.end + offset)
+    }
+}
+
+impl From<Range<i64>> for Interval {
+    fn from(r: Range<i64>) -> Self {
+        Self::new(r.start, r.end)
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// A set of integers stored as intervals.
+///
+/// Always kept normalized: intervals are sorted, non-empty and never overlap
+/// or touch so two sets with the same values always compare equal.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct RangeSet(Vec<Interval>);
+
+impl RangeSet {
+    /// The empty set.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn normalized(mut v: Vec<Interval>) -> Self {
+        v.retain(|i| !i.is_empty());
+        v.sort_unstable();
+        let mut out: Vec<Interval> = Vec::with_capacity(v.len());
+        for i in v {
+            match out.last_mut() {
+                Some(last) if i.start <= last.end => last.end = last.end.max(i.end),
+                _ => out.push(i),
+            }
+        }
+        Self(out)
+    }
+
+    /// Add every value in `i` to the set.
+    pub fn insert(&mut self, i: Interval) {
+        let mut v = std::mem::take(&mut self.0);
+        v.push(i);
+        *self = Self::normalized(v);
+    }
+
+    /// Whether the set has no values.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// How many values are in the set.
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        self.0.iter().map(|i| i.len()).sum()
+    }
+
+    /// The smallest value in the set.
+    #[must_use]
+    pub fn min(&self) -> Option<i64> {
+        self.0.first().map(|i| i.start)
+    }
+
+    /// Whether `x` is in the set.
+    #[must_use]
+    pub fn contains(&self, x: i64) -> bool {
+        // First interval which ends after x is the only one that could have it.
+        let pos = self.0.partition_point(|i| i.end <= x);
+        self.0.get(pos).is_some_and(|i| i.contains(x))
+    }
+
+    /// The intervals making up the set in order.
+    pub fn iter(&self) -> impl Iterator<Item = Interval> + '_ {
+        self.0.iter().copied()
+    }
+
+    /// Values in either set.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        Self::normalized(self.iter().chain(other.iter()).collect())
+    }
+
+    /// Values in both sets.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (a, b) = (&self.0, &other.0);
+        let mut out = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            if let Some(x) = a[i].intersect(b[j]) {
+                out.push(x);
+            }
+            // Whichever ends first can't overlap anything else in the other.
+            if a[i].end < b[j].end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self(out)
+    }
+
+    /// Values in this set but not `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let b = &other.0;
+        let mut out = vec![];
+        let mut j = 0;
+        for a in &self.0 {
+            let mut start = a.start;
+            while j < b.len() && b[j].end <= start {
+                j += 1;
+            }
+            // Walk every interval cutting into a without consuming them as
+            // the last one may also cut the next a.
+            let mut k = j;
+            while k < b.len() && b[k].start < a.end {
+                if b[k].start > start {
+                    out.push(Interval::new(start, b[k].start));
+                }
+                start = start.max(b[k].end);
+                k += 1;
+            }
+            if start < a.end {
+                out.push(Interval::new(start, a.end));
+            }
+        }
+        Self(out)
+    }
+
+    /// Split into the values below `x` and the values from `x` up.
+    #[must_use]
+    pub fn split_at(&self, x: i64) -> (Self, Self) {
+        let (lo, hi): (Vec<_>, Vec<_>) = self.iter().map(|i| i.split_at(x)).unzip();
+        (Self::normalized(lo), Self::normalized(hi))
+    }
+
+    /// Every value moved by `offset`.
+    #[must_use]
+    pub fn shift(&self, offset: i64) -> Self {
+        Self(self.iter().map(|i| i.shift(offset)).collect())
+    }
+}
+
+impl From<Interval> for RangeSet {
+    fn from(i: Interval) -> Self {
+        Self::normalized(vec![i])
+    }
+}
+
+impl FromIterator<Interval> for RangeSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        Self::normalized(iter.into_iter().collect())
+    }
+}
+
+impl fmt::Display for RangeSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        for (n, i) in self.0.iter().enumerate() {
+            if n > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{i}")?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// An `N` dimensional box: one [`Interval`] per axis.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Cuboid<const N: usize>(pub [Interval; N]);
+
+impl<const N: usize> Cuboid<N> {
+    /// The same interval along every axis.
+    #[must_use]
+    pub fn cube(side: Interval) -> Self {
+        Self([side; N])
+    }
+
+    /// Whether the box holds no points (any axis is empty).
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().any(|i| i.is_empty())
+    }
+
+    /// How many points are in the box.
+    #[must_use]
+    pub fn volume(&self) -> u128 {
+        self.0.iter().map(|i| u128::from(i.len())).product()
+    }
+
+    /// Whether `point` is in the box.
+    #[must_use]
+    pub fn contains(&self, point: [i64; N]) -> bool {
+        self.0.iter().zip(point).all(|(i, p)| i.contains(p))
+    }
+
+    /// The points in both boxes if there are any.
+    #[must_use]
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let mut out = *self;
+        for (o, i) in out.0.iter_mut().zip(other.0) {
+            *o = o.intersect(i)?;
+        }
+        Some(out)
+    }
+
+    /// Cut the box along `axis` into the points below `x` and those from `x`
+    /// up. Either half may be empty.
+    ///
+    /// # Panics
+    /// If `axis` isn't less than `N`.
+    #[must_use]
+    pub fn split_at(&self, axis: usize, x: i64) -> (Self, Self) {
+        let (lo, hi) = self.0[axis].split_at(x);
+        let (mut a, mut b) = (*self, *self);
+        a.0[axis] = lo;
+        b.0[axis] = hi;
+        (a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    fn set(v: &[(i64, i64)]) -> RangeSet {
+        v.iter().map(|&(s, e)| Interval::new(s, e)).collect()
+    }
+
+    #[test]
+    fn normalizes() {
+        let s = set(&[(5, 7), (0, 2), (2, 3), (6, 9), (4, 4)]);
+        assert_eq!(s, set(&[(0, 3), (5, 9)]));
+        assert_eq!(s.len(), 7);
+        assert_eq!(s.min(), Some(0));
+        assert!(s.contains(2) && !s.contains(3) && s.contains(8) && !s.contains(9));
+        assert_eq!(s.to_string(), "{0..3, 5..9}");
+    }
+
+    #[test]
+    fn set_ops() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25)]);
+        assert_eq!(a.union(&b), set(&[(0, 30)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 25)]));
+        assert_eq!(a.difference(&b), set(&[(0, 5), (25, 30)]));
+        assert_eq!(
+            a.split_at(22),
+            (set(&[(0, 10), (20, 22)]), set(&[(22, 30)]))
+        );
+        assert_eq!(a.shift(-5), set(&[(-5, 5), (15, 25)]));
+    }
+
+    #[test]
+    fn boxes() {
+        let c = Cuboid::<4>::cube(Interval::new(1, 4001));
+        assert_eq!(c.volume(), 4000_u128.pow(4));
+        let (lo, hi) = c.split_at(2, 2006);
+        assert_eq!(lo.0[2], Interval::new(1, 2006));
+        assert_eq!(lo.volume() + hi.volume(), c.volume());
+        assert!(lo.contains([1, 1, 2005, 1]) && !lo.contains([1, 1, 2006, 1]));
+        assert_eq!(lo.intersect(&hi), None);
+        assert!(c.split_at(0, 0).0.is_empty());
+    }
+
+    fn model(s: &RangeSet) -> BTreeSet<i64> {
+        s.iter().flat_map(|i| i.start..i.end).collect()
+    }
+
+    fn arb_set() -> impl Strategy<Value = RangeSet> {
+        prop::collection::vec((-20_i64..20, 0_i64..10), 0..6).prop_map(|v| {
+            v.into_iter()
+                .map(|(s, l)| Interval::with_len(s, l))
+                .collect()
+        })
+    }
+
+    fn is_normal(s: &RangeSet) -> bool {
+        s.0.iter().all(|i| !i.is_empty()) && s.0.windows(2).all(|w| w[0].end < w[1].start)
+    }
+
+    proptest! {
+        #[test]
+        fn ops_match_model(a in arb_set(), b in arb_set(), x in -25_i64..25) {
+            let (ma, mb) = (model(&a), model(&b));
+            for s in [a.union(&b), a.intersection(&b), a.difference(&b)] {
+                prop_assert!(is_normal(&s), "{s:?}");
+            }
+            prop_assert_eq!(model(&a.union(&b)), &ma | &mb);
+            prop_assert_eq!(model(&a.intersection(&b)), &ma & &mb);
+            prop_assert_eq!(model(&a.difference(&b)), &ma - &mb);
+            prop_assert_eq!(a.len(), ma.len() as u64);
+            prop_assert_eq!(a.contains(x), ma.contains(&x));
+
+            let (lo, hi) = a.split_at(x);
+            prop_assert!(is_normal(&lo) && is_normal(&hi));
+            prop_assert_eq!(model(&lo), ma.iter().copied().filter(|v| *v < x).collect::<BTreeSet<_>>());
+            prop_assert_eq!(model(&hi), ma.iter().copied().filter(|v| *v >= x).collect::<BTreeSet<_>>());
+            prop_assert_eq!(model(&a.shift(x)), ma.iter().map(|v| v + x).collect::<BTreeSet<_>>());
+        }
+
+        #[test]
+        fn box_split_keeps_volume(
+            sides in prop::array::uniform3((0_i64..10, 0_i64..10)),
+            axis in 0_usize..3,
+            x in -2_i64..12,
+        ) {
+            let c = Cuboid(sides.map(|(s, l)| Interval::with_len(s, l)));
+            let (lo, hi) = c.split_at(axis, x);
+            prop_assert_eq!(lo.volume() + hi.volume(), c.volume());
+            prop_assert_eq!(lo.intersect(&hi).map_or(0, |i| i.volume()), 0);
+        }
+    }
+}
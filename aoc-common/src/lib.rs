@@ -0,0 +1,25 @@
+//! Shared code for all the advent days.
+//!
+//! Every day accepts the same basic command line arguments and loads its
+//! puzzle input the same way so that lives here instead of being copied
+//! into each crate.
+pub mod animate;
+pub mod cycle;
+pub mod direction;
+pub mod geometry;
+pub mod input;
+pub mod interval;
+pub mod output;
+pub mod parse;
+pub mod render;
+pub mod search;
+pub mod solution;
+pub mod trace;
+
+pub use direction::{Compass, Direction, Heading, Step};
+pub use input::{blocks, numbered_blocks, Args, Input, INPUT_DIR_ENV};
+pub use output::{Format, Record};
+pub use parse::ParseError;
+pub use render::RenderArgs;
+pub use solution::{run, Answer, Runner, Solution};
+pub use trace::TraceArgs;
@@ -0,0 +1,258 @@
+//! Answer output shared by every day and the runner.
+//!
+//! Text is a small table meant for people. JSON and TSV carry the same
+//! fields for anything scraping the results.
+use crate::solution::Answer;
+use color_eyre::eyre::Result;
+use serde::{Serialize, Serializer};
+use std::io::{self, Write};
+use std::time::Duration;
+
+/// How to print answers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// Human readable table.
+    #[default]
+    Text,
+    /// A JSON array with one object per answer.
+    Json,
+    /// Tab separated values with a header line.
+    Tsv,
+}
+
+/// One answer (or failure) for a day.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Record {
+    /// Day number.
+    pub day: u32,
+    /// Which part this answers. Empty when the whole day failed.
+    pub part: Option<u8>,
+    /// The answer if there is one.
+    pub answer: Option<String>,
+    /// Where the input came from.
+    pub input: String,
+    /// Seconds spent parsing the input (shared by both parts).
+    #[serde(serialize_with = "secs")]
+    pub parse: Option<Duration>,
+    /// Seconds spent computing this part.
+    #[serde(serialize_with = "secs")]
+    pub elapsed: Option<Duration>,
+    /// Why there's no answer.
+    pub error: Option<String>,
+}
+
+impl Record {
+    /// Record a successful answer for `day` computed from `input`.
+    #[must_use]
+    pub fn new(day: u32, input: &str, parse: Duration, answer: &Answer) -> Self {
+        Self {
+            day,
+            part: Some(answer.part),
+            answer: Some(answer.answer.clone()),
+            input: input.to_string(),
+            parse: Some(parse),
+            elapsed: Some(answer.elapsed),
+            error: None,
+        }
+    }
+}
+
+#[allow(clippy::ref_option)]
+fn secs<S: Serializer>(d: &Option<Duration>, s: S) -> Result<S::Ok, S::Error> {
+    d.map(|d| d.as_secs_f64()).serialize(s)
+}
+
+/// Column alignment for [`table`].
+#[derive(Clone, Copy, Debug)]
+pub enum Align {
+    /// Pad on the right.
+    Left,
+    /// Pad on the left.
+    Right,
+}
+
+/// Print `records` to stdout in the given format.
+///
+/// # Errors
+/// If stdout can't be written.
+pub fn print(format: Format, records: &[Record]) -> Result<()> {
+    write(format, records, &mut io::stdout().lock())
+}
+
+/// Write `records` in the given format.
+///
+/// # Errors
+/// If `w` can't be written.
+pub fn write(format: Format, records: &[Record], w: &mut impl Write) -> Result<()> {
+    match format {
+        Format::Text => write_text(records, w)?,
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut *w, records)?;
+            writeln!(w)?;
+        }
+        Format::Tsv => {
+            writeln!(w, "day\tpart\tanswer\tinput\tparse\telapsed\terror")?;
+            for r in records {
+                let opt = |s: &Option<String>| s.as_deref().map(clean).unwrap_or_default();
+                let dur = |d: Option<Duration>| {
+                    d.map(|d| d.as_secs_f64().to_string()).unwrap_or_default()
+                };
+                writeln!(
+                    w,
+                    "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                    r.day,
+                    r.part.map(|p| p.to_string()).unwrap_or_default(),
+                    opt(&r.answer),
+                    clean(&r.input),
+                    dur(r.parse),
+                    dur(r.elapsed),
+                    opt(&r.error),
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// TSV has no quoting so tabs and newlines inside a field become spaces.
+fn clean(s: &str) -> String {
+    s.replace(['\t', '\n', '\r'], " ")
+}
+
+fn write_text(records: &[Record], w: &mut impl Write) -> io::Result<()> {
+    let fmt = |d: Option<Duration>| d.map_or_else(|| "-".to_string(), |d| format!("{d:.3?}"));
+    let rows = records
+        .iter()
+        .map(|r| {
+            vec![
+                r.day.to_string(),
+                r.part.map_or_else(|| "-".to_string(), |p| p.to_string()),
+                match (&r.answer, &r.error) {
+                    (Some(a), _) => a.clone(),
+                    (None, Some(e)) => format!("error: {e}"),
+                    (None, None) => "-".to_string(),
+                },
+                fmt(r.parse),
+                fmt(r.elapsed),
+            ]
+        })
+        .collect::<Vec<_>>();
+    table(
+        w,
+        &[
+            ("Day", Align::Right),
+            ("Part", Align::Right),
+            ("Answer", Align::Left),
+            ("Parse", Align::Right),
+            ("Time", Align::Right),
+        ],
+        &rows,
+    )?;
+
+    // Parse time repeats on each part's row so only count it once per day.
+    let mut total: Duration = records.iter().filter_map(|r| r.elapsed).sum();
+    let mut last = None;
+    for r in records {
+        if last != Some((r.day, &r.input)) {
+            total += r.parse.unwrap_or_default();
+            last = Some((r.day, &r.input));
+        }
+    }
+    writeln!(w, "\nTotal: {total:.3?}")
+}
+
+/// Write `rows` under `header` with each column padded to its widest entry.
+///
+/// # Errors
+/// If `w` can't be written.
+pub fn table(w: &mut impl Write, header: &[(&str, Align)], rows: &[Vec<String>]) -> io::Result<()> {
+    let mut widths = header.iter().map(|h| h.0.len()).collect::<Vec<_>>();
+    for r in rows {
+        for (w, s) in widths.iter_mut().zip(r) {
+            *w = (*w).max(s.chars().count());
+        }
+    }
+
+    let mut line = |cells: &[String]| {
+        let out = cells
+            .iter()
+            .zip(header)
+            .zip(&widths)
+            .map(|((c, h), w)| match h.1 {
+                Align::Left => format!("{c:<w$}"),
+                Align::Right => format!("{c:>w$}"),
+            })
+            .collect::<Vec<_>>();
+        writeln!(w, "{}", out.join("  ").trim_end())
+    };
+    line(&header.iter().map(|h| h.0.to_string()).collect::<Vec<_>>())?;
+    line(&widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>())?;
+    for r in rows {
+        line(r)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                day: 1,
+                part: Some(1),
+                answer: Some("142".into()),
+                input: "day1/example.txt".into(),
+                parse: Some(Duration::from_millis(1)),
+                elapsed: Some(Duration::from_millis(2)),
+                error: None,
+            },
+            Record {
+                day: 3,
+                input: "day3/input.txt".into(),
+                error: Some("can't read\tit".into()),
+                ..Record::default()
+            },
+        ]
+    }
+
+    fn render(format: Format) -> Result<String> {
+        let mut out = vec![];
+        write(format, &records(), &mut out)?;
+        Ok(String::from_utf8(out)?)
+    }
+
+    #[test]
+    fn json() -> Result<()> {
+        let v: serde_json::Value = serde_json::from_str(&render(Format::Json)?)?;
+        assert_eq!(v[0]["day"], 1);
+        assert_eq!(v[0]["part"], 1);
+        assert_eq!(v[0]["answer"], "142");
+        assert_eq!(v[0]["input"], "day1/example.txt");
+        assert_eq!(v[0]["elapsed"], 0.002);
+        assert!(v[1]["answer"].is_null());
+        assert_eq!(v[1]["error"], "can't read\tit");
+        Ok(())
+    }
+
+    #[test]
+    fn tsv() -> Result<()> {
+        let out = render(Format::Tsv)?;
+        let lines = out.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "day\tpart\tanswer\tinput\tparse\telapsed\terror");
+        assert_eq!(lines[1], "1\t1\t142\tday1/example.txt\t0.001\t0.002\t");
+        assert_eq!(lines[2], "3\t\t\tday3/input.txt\t\t\tcan't read it");
+        Ok(())
+    }
+
+    #[test]
+    fn text() -> Result<()> {
+        let out = render(Format::Text)?;
+        assert!(out.starts_with("Day  Part  Answer"));
+        assert!(out.contains("  1     1  142"));
+        assert!(out.contains("error: can't read"));
+        assert!(out.ends_with("Total: 3.000ms\n"));
+        Ok(())
+    }
+}
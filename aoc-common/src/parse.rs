@@ -0,0 +1,187 @@
+//! Error type for bad puzzle input.
+//!
+//! Parsers report where in the input things went wrong (line, column and the
+//! offending token) so a bad input points at the exact spot instead of a bare
+//! panic backtrace. The file isn't known while parsing so the caller fills
+//! that in afterwards with [`with_file`].
+use color_eyre::eyre::Report;
+use std::fmt;
+use std::str::FromStr;
+
+/// A problem with the puzzle input at a specific location.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// File (or `<stdin>`) the input came from if known.
+    pub file: Option<String>,
+    /// Line number starting at 1.
+    pub line: usize,
+    /// Column (in characters) starting at 1.
+    pub column: usize,
+    /// The text which couldn't be parsed.
+    pub token: String,
+    /// What was wrong with it.
+    pub message: String,
+}
+
+impl ParseError {
+    /// Create an error at an explicit line and column.
+    #[must_use]
+    pub fn new(line: usize, column: usize, token: &str, message: &str) -> Self {
+        Self {
+            file: None,
+            line,
+            column,
+            token: token.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Create an error for `token` on line number `line` whose full text is
+    /// `text`.
+    ///
+    /// If `token` is a slice of `text` (the usual case when splitting lines
+    /// up) the column is where it starts. Otherwise the error points at the
+    /// start of the line.
+    #[must_use]
+    pub fn at(line: usize, text: &str, token: &str, message: &str) -> Self {
+        let start = text.as_ptr() as usize;
+        let column = match (token.as_ptr() as usize).checked_sub(start) {
+            Some(off) if off + token.len() <= text.len() && text.is_char_boundary(off) => {
+                text[..off].chars().count() + 1
+            }
+            _ => 1,
+        };
+        Self::new(line, column, token, message)
+    }
+
+    /// Create an error for the single character at byte offset `pos` in
+    /// line number `line` whose full text is `text`.
+    #[must_use]
+    pub fn at_byte(line: usize, text: &str, pos: usize, message: &str) -> Self {
+        let token = text
+            .get(pos..)
+            .and_then(|t| t.chars().next())
+            .map_or("", |c| &text[pos..pos + c.len_utf8()]);
+        Self::at(line, text, token, message)
+    }
+
+    /// Create an error covering all of line number `line`.
+    #[must_use]
+    pub fn line(line: usize, text: &str, message: &str) -> Self {
+        Self::new(line, 1, text, message)
+    }
+
+    /// Set the file this error came from.
+    #[must_use]
+    pub fn in_file(mut self, file: &str) -> Self {
+        self.file = Some(file.to_string());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{file}:")?;
+        }
+        write!(
+            f,
+            "{}:{}: {} - {:?}",
+            self.line, self.column, self.message, self.token
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse `token` (a slice of line number `line` with text `text`) as a
+/// number or anything else implementing [`FromStr`].
+///
+/// # Errors
+/// If `token` doesn't parse as a `T`.
+pub fn number<T: FromStr>(line: usize, text: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse::<T>()
+        .map_err(|_| ParseError::at(line, text, token, "invalid number"))
+}
+
+/// Split `input` into lines which must all be the same (non-zero) length.
+/// Used by all the days where the input is a grid.
+///
+/// # Errors
+/// If the input is empty or any line's length differs from the first.
+pub fn rectangle(input: &str) -> Result<Vec<&str>, ParseError> {
+    let lines = input.lines().collect::<Vec<_>>();
+    let width = lines.first().map_or(0, |l| l.len());
+    if width == 0 {
+        return Err(ParseError::new(1, 1, "", "empty grid"));
+    }
+    for (line_num, line) in lines.iter().enumerate() {
+        if line.len() != width {
+            return Err(ParseError::line(
+                line_num + 1,
+                line,
+                &format!("expected {width} columns like the first line"),
+            ));
+        }
+    }
+    Ok(lines)
+}
+
+/// Fill in the file for `report` if it came from a [`ParseError`]. Anything
+/// else gets the file added as context instead.
+pub fn with_file(report: Report, file: &str) -> Report {
+    match report.downcast::<ParseError>() {
+        Ok(e) => e.in_file(file).into(),
+        Err(report) => report.wrap_err(file.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_from_token() {
+        let text = "Game 1: 3 blue, 4 red";
+        let e = ParseError::at(1, text, &text[10..14], "bad colour");
+        assert_eq!(e.column, 11);
+        assert_eq!(e.token, "blue");
+        assert_eq!(e.to_string(), "1:11: bad colour - \"blue\"");
+    }
+
+    #[test]
+    fn column_unrelated_token() {
+        let e = ParseError::at(3, "abc", "xyz", "nope");
+        assert_eq!(e.column, 1);
+    }
+
+    #[test]
+    fn number_error() {
+        let text = "seeds: 79 x4 55";
+        let tok = text.split_whitespace().nth(2).unwrap_or_default();
+        let e = number::<usize>(7, text, tok).unwrap_err();
+        assert_eq!((e.line, e.column, e.token.as_str()), (7, 11, "x4"));
+        assert_eq!(number::<usize>(7, text, "79"), Ok(79));
+    }
+
+    #[test]
+    fn byte_token() {
+        let e = ParseError::at_byte(4, "..#x.", 3, "bad tile");
+        assert_eq!((e.column, e.token.as_str()), (4, "x"));
+    }
+
+    #[test]
+    fn rectangle_checks() {
+        assert_eq!(rectangle("ab\ncd\n"), Ok(vec!["ab", "cd"]));
+        assert_eq!(rectangle("").unwrap_err().message, "empty grid");
+        assert_eq!(rectangle("ab\nc\n").unwrap_err().line, 2);
+    }
+
+    #[test]
+    fn file_added() {
+        let r: Report = ParseError::new(2, 5, "x", "bad").into();
+        let r = with_file(r, "day1/input.txt");
+        assert_eq!(r.to_string(), "day1/input.txt:2:5: bad - \"x\"");
+    }
+}
@@ -0,0 +1,401 @@
+//! Headless image output for visualizing grids and maps.
+//!
+//! Days build an [`Image`] from a [`Grid`] (or a map of coloured locations)
+//! and hand it to [`frame`]. When `--frames DIR` was given every call writes
+//! the next numbered file for that name (`DIR/day14-cycle-00001.png`,
+//! `...-00002.png`, etc) which can be flipped through or stitched into a
+//! video. Without it [`frame`] does nothing and never even builds the image.
+//!
+//! This works the same way as the tracing setup: the binary calls [`init`]
+//! once with the parsed [`RenderArgs`] and days don't need any extra
+//! plumbing to reach it. The same arguments also set up terminal animation
+//! (see [`crate::animate`]).
+use crate::animate::{self, AnimateArgs};
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use grid::{Grid, Location};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// A colour.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    /// Black.
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    /// White.
+    pub const WHITE: Rgb = Rgb(0xFF, 0xFF, 0xFF);
+    /// Dark grey used as the background for sparse maps.
+    pub const GREY: Rgb = Rgb(0x3F, 0x3F, 0x3F);
+    /// Red.
+    pub const RED: Rgb = Rgb(0xFF, 0, 0);
+
+    /// Parse `#RRGGBB` (the leading # is optional).
+    #[must_use]
+    pub fn from_hex(s: &str) -> Option<Self> {
+        let s = s.strip_prefix('#').unwrap_or(s);
+        if s.len() != 6 {
+            return None;
+        }
+        let c = |r| u8::from_str_radix(s.get(r)?, 16).ok();
+        Some(Rgb(c(0..2)?, c(2..4)?, c(4..6)?))
+    }
+
+    /// A shade between black (`0`) and white (`max`).
+    #[must_use]
+    pub fn grey(v: u32, max: u32) -> Self {
+        let v = u8::try_from(v.min(max) * 255 / max.max(1)).unwrap_or(u8::MAX);
+        Rgb(v, v, v)
+    }
+}
+
+/// Image file formats frames can be written as.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum ImageFormat {
+    /// Compressed PNG.
+    #[default]
+    Png,
+    /// Binary PPM (P6). Huge but trivial to write and read.
+    Ppm,
+}
+
+impl ImageFormat {
+    /// File extension for this format.
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Ppm => "ppm",
+        }
+    }
+}
+
+/// A rectangle of pixels.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// A `width` x `height` image filled with `background`.
+    #[must_use]
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// One pixel per grid cell coloured by `colour`.
+    pub fn from_grid<T>(grid: &Grid<T>, colour: impl Fn(&T) -> Rgb) -> Self {
+        let mut im = Self::new(grid.width(), grid.height(), Rgb::BLACK);
+        for (l, v) in grid {
+            im.put(&l, colour(v));
+        }
+        im
+    }
+
+    /// Draw every point in `points` on a `background` just big enough to hold
+    /// them all. The smallest x and y become the top left corner so maps with
+    /// negative coordinates work.
+    pub fn from_map<'a>(
+        points: impl IntoIterator<Item = (&'a Location, Rgb)> + Clone,
+        background: Rgb,
+    ) -> Self {
+        let (mut min, mut max) = ((isize::MAX, isize::MAX), (isize::MIN, isize::MIN));
+        for (l, _) in points.clone() {
+            min = (min.0.min(l.0), min.1.min(l.1));
+            max = (max.0.max(l.0), max.1.max(l.1));
+        }
+        if min.0 > max.0 {
+            return Self::new(0, 0, background);
+        }
+        let size = |lo: isize, hi: isize| usize::try_from(hi - lo + 1).unwrap_or(0);
+        let mut im = Self::new(size(min.0, max.0), size(min.1, max.1), background);
+        for (l, c) in points {
+            im.put(&Location(l.0 - min.0, l.1 - min.1), c);
+        }
+        im
+    }
+
+    /// Width in pixels.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height in pixels.
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The colour at `l` if that's inside the image.
+    #[must_use]
+    pub fn get(&self, l: &Location) -> Option<Rgb> {
+        self.index(l).map(|i| self.pixels[i])
+    }
+
+    /// Set the pixel at `l`. Anything outside the image is ignored so paths
+    /// can be drawn over the top without checking bounds.
+    pub fn put(&mut self, l: &Location, c: Rgb) {
+        if let Some(i) = self.index(l) {
+            self.pixels[i] = c;
+        }
+    }
+
+    fn index(&self, l: &Location) -> Option<usize> {
+        let x = usize::try_from(l.0).ok().filter(|x| *x < self.width)?;
+        let y = usize::try_from(l.1).ok().filter(|y| *y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    /// Every pixel blown up to a `scale` x `scale` square.
+    #[must_use]
+    pub fn scaled(&self, scale: usize) -> Self {
+        if scale <= 1 {
+            return self.clone();
+        }
+        let mut pixels = Vec::with_capacity(self.pixels.len() * scale * scale);
+        for row in self.pixels.chunks(self.width.max(1)) {
+            let line = row
+                .iter()
+                .flat_map(|p| std::iter::repeat_n(*p, scale))
+                .collect::<Vec<_>>();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&line);
+            }
+        }
+        Self {
+            width: self.width * scale,
+            height: self.height * scale,
+            pixels,
+        }
+    }
+
+    /// The pixels as packed RGB bytes row by row.
+    #[must_use]
+    pub fn rgb_bytes(&self) -> Vec<u8> {
+        self.pixels.iter().flat_map(|p| [p.0, p.1, p.2]).collect()
+    }
+
+    /// Write the image in the given format.
+    ///
+    /// # Errors
+    /// If writing fails or the image is too big for PNG.
+    pub fn write(&self, w: impl Write, format: ImageFormat) -> Result<()> {
+        match format {
+            ImageFormat::Png => self.write_png(w),
+            ImageFormat::Ppm => Ok(self.write_ppm(w)?),
+        }
+    }
+
+    fn write_ppm(&self, mut w: impl Write) -> io::Result<()> {
+        write!(w, "P6\n{} {}\n255\n", self.width, self.height)?;
+        w.write_all(&self.rgb_bytes())?;
+        w.flush()
+    }
+
+    fn write_png(&self, w: impl Write) -> Result<()> {
+        let mut enc = png::Encoder::new(w, u32::try_from(self.width)?, u32::try_from(self.height)?);
+        enc.set_color(png::ColorType::Rgb);
+        enc.set_depth(png::BitDepth::Eight);
+        let mut w = enc.write_header()?;
+        w.write_image_data(&self.rgb_bytes())?;
+        w.finish()?;
+        Ok(())
+    }
+
+    /// Write the image to `path`.
+    ///
+    /// # Errors
+    /// If the file can't be created or written.
+    pub fn save(&self, path: &Path, format: ImageFormat) -> Result<()> {
+        let f = File::create(path).wrap_err_with(|| format!("can't create {}", path.display()))?;
+        self.write(BufWriter::new(f), format)
+            .wrap_err_with(|| format!("can't write {}", path.display()))
+    }
+}
+
+/// Options for writing visualization frames.
+#[derive(Clone, Debug, clap::Args)]
+#[group(skip)]
+pub struct RenderArgs {
+    /// Write visualization frames into this directory (created if needed).
+    /// Nothing is drawn without it.
+    #[arg(long, value_name = "DIR")]
+    pub frames: Option<PathBuf>,
+
+    /// Image format for frames.
+    #[arg(long, value_enum, default_value_t = ImageFormat::Png)]
+    pub frame_format: ImageFormat,
+
+    /// Draw each grid cell as a square this many pixels wide.
+    #[arg(long, default_value_t = 4)]
+    pub frame_scale: usize,
+
+    /// Terminal animation.
+    #[command(flatten)]
+    pub animate: AnimateArgs,
+}
+
+impl Default for RenderArgs {
+    fn default() -> Self {
+        Self {
+            frames: None,
+            frame_format: ImageFormat::Png,
+            frame_scale: 4,
+            animate: AnimateArgs::default(),
+        }
+    }
+}
+
+/// Numbered image sequences written into one directory.
+#[derive(Debug)]
+pub struct Frames {
+    dir: PathBuf,
+    format: ImageFormat,
+    scale: usize,
+    // Next frame number for each name.
+    counts: Mutex<HashMap<String, usize>>,
+}
+
+impl Frames {
+    /// Write frames into `dir` (creating it if needed).
+    ///
+    /// # Errors
+    /// If `dir` can't be created.
+    pub fn new(dir: &Path, format: ImageFormat, scale: usize) -> Result<Self> {
+        fs::create_dir_all(dir).wrap_err_with(|| format!("can't create {}", dir.display()))?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            format,
+            scale,
+            counts: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Write `image` as the next frame for `name` and return where it went.
+    /// Numbering starts at `1` separately for each name.
+    ///
+    /// # Errors
+    /// If the file can't be written.
+    ///
+    /// # Panics
+    /// If another thread panicked while writing a frame.
+    pub fn write(&self, name: &str, image: &Image) -> Result<PathBuf> {
+        let n = {
+            let mut counts = self.counts.lock().unwrap();
+            let n = counts.entry(name.to_string()).or_insert(0);
+            *n += 1;
+            *n
+        };
+        let path = self
+            .dir
+            .join(format!("{name}-{n:05}.{}", self.format.extension()));
+        image.scaled(self.scale).save(&path, self.format)?;
+        Ok(path)
+    }
+}
+
+static FRAMES: OnceLock<Frames> = OnceLock::new();
+
+/// Set up frame output and terminal animation from the command line. Frames
+/// are only written if `--frames` was given.
+///
+/// # Errors
+/// If the directory can't be created or this was already called.
+pub fn init(args: &RenderArgs) -> Result<()> {
+    animate::init(&args.animate);
+    let Some(dir) = &args.frames else {
+        return Ok(());
+    };
+    let frames = Frames::new(dir, args.frame_format, args.frame_scale)?;
+    FRAMES
+        .set(frames)
+        .map_err(|_| eyre!("frame output already set up"))
+}
+
+/// Whether frames are being written. Useful to skip work only needed for
+/// drawing.
+#[must_use]
+pub fn enabled() -> bool {
+    FRAMES.get().is_some()
+}
+
+/// Write the next frame for `name` if frames are enabled. `image` is only
+/// called when they are.
+///
+/// # Errors
+/// If the frame can't be written.
+pub fn frame(name: &str, image: impl FnOnce() -> Image) -> Result<()> {
+    if let Some(f) = FRAMES.get() {
+        f.write(name, &image())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colours() {
+        assert_eq!(Rgb::from_hex("#70c710"), Some(Rgb(0x70, 0xC7, 0x10)));
+        assert_eq!(Rgb::from_hex("0a0b0c"), Some(Rgb(10, 11, 12)));
+        assert_eq!(Rgb::from_hex("#12345"), None);
+        assert_eq!(Rgb::from_hex("#12345g"), None);
+        assert_eq!(Rgb::grey(9, 9), Rgb::WHITE);
+        assert_eq!(Rgb::grey(0, 9), Rgb::BLACK);
+    }
+
+    #[test]
+    fn building() {
+        let mut g = Grid::<u32>::new(3, 2);
+        g.add(&Location(2, 1), 9);
+        let im = Image::from_grid(&g, |v| Rgb::grey(*v, 9));
+        assert_eq!((im.width(), im.height()), (3, 2));
+        assert_eq!(im.get(&Location(2, 1)), Some(Rgb::WHITE));
+        assert_eq!(im.get(&Location(3, 1)), None);
+
+        let pts = [(Location(-2, 5), Rgb::RED), (Location(1, 6), Rgb::WHITE)];
+        let im = Image::from_map(pts.iter().map(|(l, c)| (l, *c)), Rgb::GREY);
+        assert_eq!((im.width(), im.height()), (4, 2));
+        assert_eq!(im.get(&Location(0, 0)), Some(Rgb::RED));
+        assert_eq!(im.get(&Location(3, 1)), Some(Rgb::WHITE));
+        assert_eq!(im.get(&Location(1, 0)), Some(Rgb::GREY));
+
+        let big = im.scaled(3);
+        assert_eq!((big.width(), big.height()), (12, 6));
+        assert_eq!(big.get(&Location(11, 5)), Some(Rgb::WHITE));
+        assert_eq!(big.get(&Location(2, 2)), Some(Rgb::RED));
+    }
+
+    #[test]
+    fn numbered_files() {
+        let dir = std::env::temp_dir().join(format!("aoc-render-{}", std::process::id()));
+        let im = Image::new(2, 1, Rgb::RED);
+
+        let f = Frames::new(&dir, ImageFormat::Ppm, 1).unwrap();
+        let first = f.write("test", &im).unwrap();
+        let second = f.write("test", &im).unwrap();
+        assert!(first.ends_with("test-00001.ppm"));
+        assert!(second.ends_with("test-00002.ppm"));
+        assert_eq!(
+            fs::read(&first).unwrap(),
+            b"P6\n2 1\n255\n\xFF\x00\x00\xFF\x00\x00"
+        );
+
+        let f = Frames::new(&dir, ImageFormat::Png, 2).unwrap();
+        let png = fs::read(f.write("test", &im).unwrap()).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
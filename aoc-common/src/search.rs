@@ -0,0 +1,312 @@
+//! Shortest path searches over any state space.
+//!
+//! A day describes its puzzle by implementing [`Problem`] (what the states
+//! are, which moves are possible from each and what they cost, and when
+//! it's done) and then picks a search:
+//!
+//! - [`dijkstra`] for general non-negative costs.
+//! - [`astar`] when [`Problem::heuristic`] gives a useful lower bound.
+//! - [`bucket`] (Dial's algorithm) when costs are small integers.
+//! - [`bfs`] when every move costs the same.
+//!
+//! All of them return the cheapest [`Path`] to a goal (cost and every state
+//! along the way) or None if no goal can be reached.
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// A search problem.
+pub trait Problem {
+    /// Everything needed to know where the search is and what moves are
+    /// possible next.
+    type State: Clone + Eq + Hash;
+
+    /// Every state reachable in one move from `state` along with what that
+    /// move costs.
+    fn successors(&self, state: &Self::State) -> Vec<(Self::State, u64)>;
+
+    /// Whether `state` is somewhere the search can stop.
+    fn is_goal(&self, state: &Self::State) -> bool;
+
+    /// A lower bound on the cost from `state` to the nearest goal. Only
+    /// [`astar`] uses this and it must never overestimate. Defaults to 0
+    /// which makes A* the same as Dijkstra.
+    fn heuristic(&self, _state: &Self::State) -> u64 {
+        0
+    }
+}
+
+/// The result of a successful search.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<S> {
+    /// Total cost of every move.
+    pub cost: u64,
+    /// Every state from the start to the goal inclusive.
+    pub states: Vec<S>,
+}
+
+// States are kept in one place and referred to by index so the queues don't
+// need them to be Ord.
+struct Search<S> {
+    states: Vec<S>,
+    ids: HashMap<S, usize>,
+    cost: Vec<u64>,
+    parent: Vec<Option<usize>>,
+}
+
+impl<S: Clone + Eq + Hash> Search<S> {
+    fn new() -> Self {
+        Self {
+            states: vec![],
+            ids: HashMap::new(),
+            cost: vec![],
+            parent: vec![],
+        }
+    }
+
+    // Record reaching `state` for `cost` from `parent`. Returns its id if
+    // that's better than any way found so far.
+    fn relax(&mut self, state: S, cost: u64, parent: Option<usize>) -> Option<usize> {
+        match self.ids.get(&state) {
+            Some(&id) if self.cost[id] <= cost => None,
+            Some(&id) => {
+                self.cost[id] = cost;
+                self.parent[id] = parent;
+                Some(id)
+            }
+            None => {
+                let id = self.states.len();
+                self.ids.insert(state.clone(), id);
+                self.states.push(state);
+                self.cost.push(cost);
+                self.parent.push(parent);
+                Some(id)
+            }
+        }
+    }
+
+    fn path(&self, goal: usize) -> Path<S> {
+        let mut states = vec![];
+        let mut cur = Some(goal);
+        while let Some(id) = cur {
+            states.push(self.states[id].clone());
+            cur = self.parent[id];
+        }
+        states.reverse();
+        Path {
+            cost: self.cost[goal],
+            states,
+        }
+    }
+}
+
+/// Dijkstra's algorithm from any of `starts`.
+pub fn dijkstra<P: Problem>(
+    problem: &P,
+    starts: impl IntoIterator<Item = P::State>,
+) -> Option<Path<P::State>> {
+    best_first(problem, starts, |_| 0)
+}
+
+/// A* from any of `starts` guided by [`Problem::heuristic`].
+pub fn astar<P: Problem>(
+    problem: &P,
+    starts: impl IntoIterator<Item = P::State>,
+) -> Option<Path<P::State>> {
+    best_first(problem, starts, |s| problem.heuristic(s))
+}
+
+fn best_first<P: Problem>(
+    problem: &P,
+    starts: impl IntoIterator<Item = P::State>,
+    h: impl Fn(&P::State) -> u64,
+) -> Option<Path<P::State>> {
+    let mut s = Search::new();
+    let mut q = BinaryHeap::new();
+    for start in starts {
+        let est = h(&start);
+        if let Some(id) = s.relax(start, 0, None) {
+            q.push(Reverse((est, id)));
+        }
+    }
+
+    while let Some(Reverse((est, id))) = q.pop() {
+        let state = s.states[id].clone();
+        let cost = s.cost[id];
+        // A cheaper way here was found after this was queued.
+        if est != cost + h(&state) {
+            continue;
+        }
+        if problem.is_goal(&state) {
+            return Some(s.path(id));
+        }
+        for (next, step) in problem.successors(&state) {
+            let c = cost + step;
+            let est = c + h(&next);
+            if let Some(n) = s.relax(next, c, Some(id)) {
+                q.push(Reverse((est, n)));
+            }
+        }
+    }
+    None
+}
+
+/// Dial's algorithm: Dijkstra using a bucket per total cost instead of a
+/// heap. Faster when move costs are small integers (such as the digits in a
+/// grid) but memory grows with the total cost of the answer.
+pub fn bucket<P: Problem>(
+    problem: &P,
+    starts: impl IntoIterator<Item = P::State>,
+) -> Option<Path<P::State>> {
+    let mut s = Search::new();
+    let mut buckets: Vec<Vec<usize>> = vec![vec![]];
+    for start in starts {
+        if let Some(id) = s.relax(start, 0, None) {
+            buckets[0].push(id);
+        }
+    }
+
+    let mut cost = 0;
+    while cost < buckets.len() {
+        // Zero cost moves add to the bucket being worked on so keep going
+        // until it's really empty.
+        while let Some(id) = buckets[cost].pop() {
+            if usize::try_from(s.cost[id]).ok() != Some(cost) {
+                continue;
+            }
+            let state = s.states[id].clone();
+            if problem.is_goal(&state) {
+                return Some(s.path(id));
+            }
+            for (next, step) in problem.successors(&state) {
+                let c = s.cost[id] + step;
+                if let Some(n) = s.relax(next, c, Some(id)) {
+                    let Ok(b) = usize::try_from(c) else {
+                        continue;
+                    };
+                    if b >= buckets.len() {
+                        buckets.resize_with(b + 1, Vec::new);
+                    }
+                    buckets[b].push(n);
+                }
+            }
+        }
+        cost += 1;
+    }
+    None
+}
+
+/// Breadth first search from any of `starts`. Move costs are ignored and
+/// the returned cost is the number of moves.
+pub fn bfs<P: Problem>(
+    problem: &P,
+    starts: impl IntoIterator<Item = P::State>,
+) -> Option<Path<P::State>> {
+    let mut s = Search::new();
+    let mut q = VecDeque::new();
+    for start in starts {
+        if let Some(id) = s.relax(start, 0, None) {
+            q.push_back(id);
+        }
+    }
+
+    while let Some(id) = q.pop_front() {
+        let state = s.states[id].clone();
+        if problem.is_goal(&state) {
+            return Some(s.path(id));
+        }
+        let c = s.cost[id] + 1;
+        for (next, _) in problem.successors(&state) {
+            // The first time a state is seen is always the fewest moves.
+            if s.ids.contains_key(&next) {
+                continue;
+            }
+            if let Some(n) = s.relax(next, c, Some(id)) {
+                q.push_back(n);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A small weighted graph where the direct edge is a trap:
+    //   0 -10-> 3, 0 -1-> 1 -1-> 2 -1-> 3, 2 -0-> 4 -0-> 3
+    struct Graph;
+
+    impl Problem for Graph {
+        type State = u8;
+
+        fn successors(&self, s: &u8) -> Vec<(u8, u64)> {
+            match s {
+                0 => vec![(3, 10), (1, 1)],
+                1 => vec![(2, 1)],
+                2 => vec![(3, 1), (4, 0)],
+                4 => vec![(3, 0)],
+                _ => vec![],
+            }
+        }
+
+        fn is_goal(&self, s: &u8) -> bool {
+            *s == 3
+        }
+    }
+
+    // Walk from (0, 0) to (n, n) on an open grid where moving right costs
+    // 1 and down costs 2.
+    struct Open(i32);
+
+    impl Problem for Open {
+        type State = (i32, i32);
+
+        fn successors(&self, s: &(i32, i32)) -> Vec<((i32, i32), u64)> {
+            let mut v = vec![];
+            if s.0 < self.0 {
+                v.push(((s.0 + 1, s.1), 1));
+            }
+            if s.1 < self.0 {
+                v.push(((s.0, s.1 + 1), 2));
+            }
+            v
+        }
+
+        fn is_goal(&self, s: &(i32, i32)) -> bool {
+            *s == (self.0, self.0)
+        }
+
+        fn heuristic(&self, s: &(i32, i32)) -> u64 {
+            u64::from((self.0 - s.0).unsigned_abs() + 2 * (self.0 - s.1).unsigned_abs())
+        }
+    }
+
+    #[test]
+    fn weighted() {
+        let want = Path {
+            cost: 2,
+            states: vec![0, 1, 2, 4, 3],
+        };
+        assert_eq!(dijkstra(&Graph, [0]), Some(want.clone()));
+        assert_eq!(astar(&Graph, [0]), Some(want.clone()));
+        assert_eq!(bucket(&Graph, [0]), Some(want));
+        assert_eq!(dijkstra(&Graph, [5]), None);
+    }
+
+    #[test]
+    fn fewest_moves() {
+        let p = bfs(&Graph, [0]).unwrap();
+        assert_eq!((p.cost, p.states), (1, vec![0, 3]));
+    }
+
+    #[test]
+    fn all_agree() {
+        let d = dijkstra(&Open(6), [(0, 0)]).unwrap();
+        assert_eq!(d.cost, 18);
+        assert_eq!(d.states.len(), 13);
+        assert_eq!(astar(&Open(6), [(0, 0)]).unwrap().cost, 18);
+        assert_eq!(bucket(&Open(6), [(0, 0)]).unwrap().cost, 18);
+        assert_eq!(bfs(&Open(6), [(0, 0)]).unwrap().cost, 12);
+    }
+}
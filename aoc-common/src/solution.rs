@@ -0,0 +1,116 @@
+//! The trait every day implements along with a simple driver for it.
+use crate::input::Input;
+use crate::output::{self, Format, Record};
+use crate::parse;
+use color_eyre::eyre::{eyre, Result};
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+use tracing::{info, info_span};
+
+/// A day's puzzle split into parsing and the two parts.
+///
+/// Anything configurable about a day (the bag contents for day2, map display,
+/// etc) lives on the implementing type so the same parsed input can be run
+/// with different settings.
+pub trait Solution {
+    /// Which day this solves.
+    const DAY: u32;
+    /// Parsed form of the puzzle input shared by both parts.
+    type Input;
+    /// Answer type for both parts.
+    type Output: Display;
+
+    /// Parse the raw puzzle text.
+    ///
+    /// # Errors
+    /// If the text isn't a valid input for this day.
+    fn parse(&self, input: &str) -> Result<Self::Input>;
+
+    /// Solve part 1 for an already parsed input.
+    ///
+    /// # Errors
+    /// If no answer can be computed for this input.
+    fn part1(&self, input: &Self::Input) -> Result<Self::Output>;
+
+    /// Solve part 2 for an already parsed input.
+    ///
+    /// # Errors
+    /// If no answer can be computed for this input.
+    fn part2(&self, input: &Self::Input) -> Result<Self::Output>;
+}
+
+/// Read `input`, solve both parts with the given solution and print the
+/// answers in `format`.
+///
+/// # Errors
+/// If reading, parsing or either part fails. Parse errors name the input
+/// they came from.
+pub fn run<S: Solution>(solution: &S, input: &Input, format: Format) -> Result<()> {
+    let text = input.string()?;
+    let (parse, answers) = solution
+        .run_parts(&text, &[1, 2])
+        .map_err(|e| parse::with_file(e, &input.to_string()))?;
+    let records = answers
+        .iter()
+        .map(|a| Record::new(S::DAY, &input.to_string(), parse, a))
+        .collect::<Vec<_>>();
+    output::print(format, &records)
+}
+
+/// One part's answer along with how long it took to compute.
+#[derive(Clone, Debug)]
+pub struct Answer {
+    /// Which part (1 or 2) this answers.
+    pub part: u8,
+    /// The answer formatted for display.
+    pub answer: String,
+    /// Wall clock time spent in the part itself (parsing not included).
+    pub elapsed: Duration,
+}
+
+/// Object safe view of a [`Solution`] so a single binary can hold every day
+/// behind a `Box<dyn Runner>` regardless of their input and output types.
+///
+/// This is implemented for every [`Solution`] so days never implement it
+/// directly. Everything a day logs happens inside a `day` span and then a
+/// `parse` or `part` span so events can be told apart.
+pub trait Runner {
+    /// Parse `text` and then run the requested parts in order.
+    ///
+    /// The returned duration is the time spent parsing.
+    ///
+    /// # Errors
+    /// If parsing or any requested part fails or a part other than 1 or 2 is
+    /// asked for.
+    fn run_parts(&self, text: &str, parts: &[u8]) -> Result<(Duration, Vec<Answer>)>;
+}
+
+impl<S: Solution> Runner for S {
+    fn run_parts(&self, text: &str, parts: &[u8]) -> Result<(Duration, Vec<Answer>)> {
+        let _day = info_span!("day", day = S::DAY).entered();
+        let now = Instant::now();
+        let input = info_span!("parse").in_scope(|| self.parse(text))?;
+        let parse = now.elapsed();
+        info!(elapsed = ?parse, "parsed");
+
+        let mut ret = vec![];
+        for part in parts {
+            let _part = info_span!("part", part).entered();
+            let now = Instant::now();
+            let answer = match part {
+                1 => self.part1(&input)?,
+                2 => self.part2(&input)?,
+                _ => return Err(eyre!("invalid part {part}")),
+            };
+            // Before logging and formatting so only the part itself counts.
+            let elapsed = now.elapsed();
+            info!(%answer, ?elapsed, "solved");
+            ret.push(Answer {
+                part: *part,
+                answer: answer.to_string(),
+                elapsed,
+            });
+        }
+        Ok((parse, ret))
+    }
+}
@@ -0,0 +1,147 @@
+//! Logging for every day via [`tracing`].
+//!
+//! Days emit events (`debug!`, `trace!`, etc) instead of printing when some
+//! flag is set. Which ones show up is decided once at startup from
+//! `--log-level` (or `--debug`, or `RUST_LOG`) so noisy output such as
+//! day16's per-step lines can be turned on for just the day being looked at
+//! with something like `--log-level day16=trace`.
+//!
+//! Events go to stderr so they never mix with the answers. With
+//! `--trace-file` they're also written there as JSON lines (one object per
+//! event including the day/part spans it happened in).
+use color_eyre::eyre::{Result, WrapErr};
+use grid::{Grid, Location};
+use std::env;
+use std::fmt::{Display, Write};
+use std::fs::File;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, EnvFilter, Layer};
+
+/// Filter used when nothing else asks for one.
+pub const DEFAULT_FILTER: &str = "warn";
+
+/// Logging options shared by every binary.
+#[derive(Clone, Debug, Default, clap::Args)]
+#[group(skip)]
+pub struct TraceArgs {
+    /// Shorthand for --log-level debug.
+    #[arg(long, default_value_t = false)]
+    pub debug: bool,
+
+    /// What to log. Either a level (error, warn, info, debug, trace) or
+    /// per-target directives like `day16=trace,warn`. Overrides `RUST_LOG`.
+    #[arg(long)]
+    pub log_level: Option<String>,
+
+    /// Also write every logged event to this file as JSON lines.
+    #[arg(long)]
+    pub trace_file: Option<PathBuf>,
+}
+
+impl TraceArgs {
+    /// The filter directives these options ask for given the value of
+    /// `RUST_LOG` (if set).
+    ///
+    /// `--log-level` wins, then `--debug`, then `RUST_LOG` and finally
+    /// [`DEFAULT_FILTER`].
+    #[must_use]
+    pub fn directives(&self, rust_log: Option<&str>) -> String {
+        if let Some(l) = &self.log_level {
+            return l.clone();
+        }
+        if self.debug {
+            return "debug".to_string();
+        }
+        match rust_log {
+            Some(l) if !l.is_empty() => l.to_string(),
+            _ => DEFAULT_FILTER.to_string(),
+        }
+    }
+}
+
+/// Install the global subscriber described by `args`.
+///
+/// # Errors
+/// If the filter doesn't parse, the trace file can't be created or a
+/// subscriber was already installed.
+pub fn init(args: &TraceArgs) -> Result<()> {
+    let directives = args.directives(env::var(EnvFilter::DEFAULT_ENV).ok().as_deref());
+    let filter = || {
+        EnvFilter::try_new(&directives).wrap_err_with(|| format!("bad log filter {directives:?}"))
+    };
+
+    let stderr = fmt::layer()
+        .with_writer(io::stderr)
+        .without_time()
+        .with_filter(filter()?);
+    let file = match &args.trace_file {
+        Some(path) => {
+            let f = File::create(path)
+                .wrap_err_with(|| format!("can't create trace file {}", path.display()))?;
+            Some(
+                fmt::layer()
+                    .json()
+                    .with_writer(Mutex::new(f))
+                    .with_filter(filter()?),
+            )
+        }
+        None => None,
+    };
+
+    tracing_subscriber::registry()
+        .with(stderr)
+        .with(file)
+        .try_init()
+        .wrap_err("can't install the log subscriber")
+}
+
+/// Render `grid` as text (one line per row) for logging.
+#[must_use]
+pub fn grid<T: Display>(grid: &Grid<T>) -> String {
+    let mut out = String::new();
+    for y in 0..grid.height() {
+        for x in 0..grid.width() {
+            let l = Location(
+                isize::try_from(x).unwrap_or(isize::MAX),
+                isize::try_from(y).unwrap_or(isize::MAX),
+            );
+            // Writing to a String can't fail.
+            let _ = write!(out, "{}", grid.get(&l));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directive_order() {
+        let mut args = TraceArgs::default();
+        assert_eq!(args.directives(None), DEFAULT_FILTER);
+        assert_eq!(args.directives(Some("")), DEFAULT_FILTER);
+        assert_eq!(args.directives(Some("day16=trace")), "day16=trace");
+        args.debug = true;
+        assert_eq!(args.directives(Some("day16=trace")), "debug");
+        args.log_level = Some("info".into());
+        assert_eq!(args.directives(Some("day16=trace")), "info");
+    }
+
+    #[test]
+    fn grid_rows() {
+        let mut g = Grid::<char>::new(3, 2);
+        for (x, y, c) in [(0, 0, 'a'), (1, 0, 'b'), (2, 0, 'c')] {
+            g.add(&Location(x, y), c);
+        }
+        for x in 0..3 {
+            g.add(&Location(x, 1), '.');
+        }
+        assert_eq!(grid(&g), "abc\n...\n");
+    }
+}
@@ -0,0 +1,182 @@
+//! Benchmarks for parsing and both parts of every implemented day.
+//!
+//! These run against each day's local input.txt (resolved the same way as
+//! `aoc run`, so `AOC_INPUT_DIR` works) or whatever file `AOC_BENCH_INPUT`
+//! names instead. Days without that file are skipped so this works offline
+//! with just the checked in examples (`AOC_BENCH_INPUT=example.txt`).
+//!
+//! Setting `AOC_BENCH_GEN` benchmarks generated inputs instead (see `aoc
+//! gen`). Its value is the size to generate (empty for each day's default)
+//! and `AOC_BENCH_SEED` picks the seed (0 when not set).
+//!
+//! Besides criterion's normal output a summary table of mean times per day
+//! is printed at the end.
+use aoc::days::{self, Day};
+use aoc::generate;
+use aoc_common::output::{self, Align};
+use aoc_common::Solution;
+use criterion::{black_box, Bencher, Criterion};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::env;
+use std::io;
+use std::time::{Duration, Instant};
+
+// (day, stage) -> total time and iterations seen.
+type Totals = BTreeMap<(u32, &'static str), (Duration, u64)>;
+
+#[derive(Default)]
+struct Summary(RefCell<Totals>);
+
+impl Summary {
+    // Time `f` the way criterion asks while also keeping a running total for
+    // the summary.
+    fn time<O>(&self, b: &mut Bencher, day: u32, stage: &'static str, f: impl Fn() -> O) {
+        b.iter_custom(|iters| {
+            let now = Instant::now();
+            for _ in 0..iters {
+                black_box(f());
+            }
+            let elapsed = now.elapsed();
+            let mut m = self.0.borrow_mut();
+            let e = m.entry((day, stage)).or_default();
+            e.0 += elapsed;
+            e.1 += iters;
+            elapsed
+        });
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn mean(&self, day: u32, stage: &'static str) -> String {
+        match self.0.borrow().get(&(day, stage)) {
+            Some((total, iters)) if *iters > 0 => {
+                format!("{:.3?}", total.div_f64(*iters as f64))
+            }
+            _ => "-".to_string(),
+        }
+    }
+
+    fn print(&self) -> io::Result<()> {
+        let mut days = self.0.borrow().keys().map(|k| k.0).collect::<Vec<_>>();
+        days.dedup();
+        let rows = days
+            .into_iter()
+            .map(|d| {
+                vec![
+                    d.to_string(),
+                    self.mean(d, "parse"),
+                    self.mean(d, "part1"),
+                    self.mean(d, "part2"),
+                ]
+            })
+            .collect::<Vec<_>>();
+        println!();
+        output::table(
+            &mut io::stdout().lock(),
+            &[
+                ("Day", Align::Right),
+                ("Parse", Align::Right),
+                ("Part1", Align::Right),
+                ("Part2", Align::Right),
+            ],
+            &rows,
+        )
+    }
+}
+
+// Where each day's input comes from.
+enum Source {
+    File(String),
+    Generated { size: Option<usize>, seed: u64 },
+}
+
+impl Source {
+    fn from_env() -> Self {
+        match env::var("AOC_BENCH_GEN") {
+            Ok(size) => Source::Generated {
+                size: size.parse().ok(),
+                seed: env::var("AOC_BENCH_SEED")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(0),
+            },
+            Err(_) => Source::File(
+                env::var("AOC_BENCH_INPUT").unwrap_or_else(|_| "input.txt".to_string()),
+            ),
+        }
+    }
+
+    // The text for `d` or why there isn't any.
+    fn text(&self, d: &Day) -> Result<String, String> {
+        match self {
+            Source::File(filename) => {
+                let input = d.input(filename);
+                input.string().map_err(|_| format!("can't read {input}"))
+            }
+            Source::Generated { size, seed } => {
+                let g = generate::get(d.number).ok_or("no generator")?;
+                Ok(g.generate(size.unwrap_or(g.default_size), *seed))
+            }
+        }
+    }
+}
+
+fn bench<S: Solution>(c: &mut Criterion, summary: &Summary, source: &Source, day: &S) {
+    let Some(d) = days::get(S::DAY) else {
+        return;
+    };
+    let text = match source.text(d) {
+        Ok(t) => t,
+        Err(e) => {
+            eprintln!("skipping {} - {e}", d.name());
+            return;
+        }
+    };
+    let parsed = match day.parse(&text) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("skipping {} - {e:#}", d.name());
+            return;
+        }
+    };
+
+    let mut g = c.benchmark_group(d.name());
+    g.bench_function("parse", |b| {
+        summary.time(b, S::DAY, "parse", || day.parse(black_box(&text)));
+    });
+    g.bench_function("part1", |b| {
+        summary.time(b, S::DAY, "part1", || day.part1(black_box(&parsed)));
+    });
+    g.bench_function("part2", |b| {
+        summary.time(b, S::DAY, "part2", || day.part2(black_box(&parsed)));
+    });
+    g.finish();
+}
+
+fn main() -> io::Result<()> {
+    let source = Source::from_env();
+    let mut c = Criterion::default().configure_from_args();
+    let s = Summary::default();
+
+    bench(&mut c, &s, &source, &day1::Day1::default());
+    bench(&mut c, &s, &source, &day2::Day2::default());
+    bench(&mut c, &s, &source, &day3::Day3);
+    bench(&mut c, &s, &source, &day4::Day4);
+    bench(&mut c, &s, &source, &day5::Day5);
+    bench(&mut c, &s, &source, &day6::Day6);
+    bench(&mut c, &s, &source, &day7::Day7);
+    bench(&mut c, &s, &source, &day8::Day8);
+    bench(&mut c, &s, &source, &day9::Day9);
+    bench(&mut c, &s, &source, &day10::Day10);
+    bench(&mut c, &s, &source, &day11::Day11);
+    bench(&mut c, &s, &source, &day13::Day13);
+    bench(&mut c, &s, &source, &day14::Day14);
+    bench(&mut c, &s, &source, &day15::Day15);
+    bench(&mut c, &s, &source, &day16::Day16);
+    bench(&mut c, &s, &source, &day17::Day17);
+    bench(&mut c, &s, &source, &day18::Day18::default());
+    bench(&mut c, &s, &source, &day19::Day19);
+
+    c.final_summary();
+    s.print()
+}
@@ -0,0 +1,129 @@
+//! Registry of every day the runner knows about.
+use aoc_common::{Input, Runner};
+use std::path::Path;
+
+/// Settings passed along to whichever day is being run.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    /// Spelled out digits for day1.
+    pub day1: day1::Options,
+    /// Bag contents for day2.
+    pub day2: day2::Options,
+    /// Map display options for day18.
+    pub day18: day18::Options,
+}
+
+/// A single day in the registry.
+#[derive(Clone, Copy, Debug)]
+pub struct Day {
+    /// Day number (1-25).
+    pub number: u32,
+    /// Puzzle title.
+    pub title: &'static str,
+    /// Whether the day has been solved yet. Unsolved days are skipped by
+    /// `run --all`.
+    pub implemented: bool,
+}
+
+impl Day {
+    /// Name of the crate (and directory) for this day.
+    #[must_use]
+    pub fn name(&self) -> String {
+        format!("day{}", self.number)
+    }
+
+    /// Resolve `filename` the same way the day's own binary would.
+    #[must_use]
+    pub fn input(&self, filename: &str) -> Input {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join(self.name());
+        Input::new(filename, &dir.to_string_lossy())
+    }
+
+    /// Build the solution for this day with the given options.
+    ///
+    /// # Panics
+    /// If this isn't one of the days in [`DAYS`].
+    #[must_use]
+    pub fn solver(&self, opts: &Options) -> Box<dyn Runner> {
+        match self.number {
+            1 => Box::new(day1::Day1 {
+                options: opts.day1.clone(),
+            }),
+            2 => Box::new(day2::Day2 {
+                options: opts.day2.clone(),
+            }),
+            3 => Box::new(day3::Day3),
+            4 => Box::new(day4::Day4),
+            5 => Box::new(day5::Day5),
+            6 => Box::new(day6::Day6),
+            7 => Box::new(day7::Day7),
+            8 => Box::new(day8::Day8),
+            9 => Box::new(day9::Day9),
+            10 => Box::new(day10::Day10),
+            11 => Box::new(day11::Day11),
+            12 => Box::new(day12::Day12),
+            13 => Box::new(day13::Day13),
+            14 => Box::new(day14::Day14),
+            15 => Box::new(day15::Day15),
+            16 => Box::new(day16::Day16),
+            17 => Box::new(day17::Day17),
+            18 => Box::new(day18::Day18 {
+                options: opts.day18.clone(),
+            }),
+            19 => Box::new(day19::Day19),
+            20 => Box::new(day20::Day20),
+            21 => Box::new(day21::Day21),
+            22 => Box::new(day22::Day22),
+            23 => Box::new(day23::Day23),
+            24 => Box::new(day24::Day24),
+            25 => Box::new(day25::Day25),
+            _ => unreachable!("day {} isn't in the registry", self.number),
+        }
+    }
+}
+
+/// Every day in order. Index is day - 1.
+pub const DAYS: [Day; 25] = [
+    day(1, "Trebuchet calibration", true),
+    day(2, "Cube conundrum", true),
+    day(3, "Gear ratios", true),
+    day(4, "Scratchcards", true),
+    day(5, "Seed fertilizer almanac", true),
+    day(6, "Boat races", true),
+    day(7, "Camel cards", true),
+    day(8, "Haunted wasteland", true),
+    day(9, "Mirage maintenance", true),
+    day(10, "Pipe maze", true),
+    day(11, "Cosmic expansion", true),
+    day(12, "", false),
+    day(13, "Point of incidence", true),
+    day(14, "Parabolic reflector dish", true),
+    day(15, "Lens library", true),
+    day(16, "The floor will be lava", true),
+    day(17, "Clumsy crucible", true),
+    day(18, "Lavaduct lagoon", true),
+    day(19, "Aplenty", true),
+    day(20, "", false),
+    day(21, "", false),
+    day(22, "", false),
+    day(23, "", false),
+    day(24, "", false),
+    day(25, "", false),
+];
+
+const fn day(number: u32, title: &'static str, implemented: bool) -> Day {
+    Day {
+        number,
+        title,
+        implemented,
+    }
+}
+
+/// Look up a day by number.
+#[must_use]
+pub fn get(day: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == day)
+}
@@ -0,0 +1,949 @@
+//! Synthetic puzzle inputs of any size.
+//!
+//! There's only one real input per day which makes performance work and edge
+//! cases guesswork. Each generator here builds an input from a seed which is
+//! structurally valid for its day (loops close, mirrors reflect, workflows
+//! never cycle, ...) so both parts can always be solved no matter the size.
+//!
+//! The same day, size and seed always give the same text so a generated
+//! input can be recreated instead of checked in. `aoc gen` writes them out
+//! and the benchmarks can use them in place of input.txt.
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Write as _;
+
+/// Builds inputs for one day.
+#[derive(Clone, Copy, Debug)]
+pub struct Generator {
+    /// Day the inputs are for.
+    pub day: u32,
+    /// What the size counts.
+    pub size: &'static str,
+    /// A size close to the real puzzle input.
+    pub default_size: usize,
+    build: fn(&mut StdRng, usize) -> String,
+}
+
+impl Generator {
+    /// An input of about `size` built from `seed`.
+    ///
+    /// Sizes too small for a valid input are rounded up and a few days cap
+    /// them so the answers still fit in 64 bits.
+    #[must_use]
+    pub fn generate(&self, size: usize, seed: u64) -> String {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (self.build)(&mut rng, size)
+    }
+}
+
+/// Every day with a generator in order.
+pub const GENERATORS: [Generator; 18] = [
+    Generator {
+        day: 1,
+        size: "lines",
+        default_size: 1000,
+        build: day1,
+    },
+    Generator {
+        day: 2,
+        size: "games",
+        default_size: 100,
+        build: day2,
+    },
+    Generator {
+        day: 3,
+        size: "rows and columns",
+        default_size: 140,
+        build: day3,
+    },
+    Generator {
+        day: 4,
+        size: "cards",
+        default_size: 200,
+        build: day4,
+    },
+    Generator {
+        day: 5,
+        size: "ranges per map",
+        default_size: 30,
+        build: day5,
+    },
+    Generator {
+        day: 6,
+        size: "races (at most 4)",
+        default_size: 4,
+        build: day6,
+    },
+    Generator {
+        day: 7,
+        size: "hands",
+        default_size: 1000,
+        build: day7,
+    },
+    Generator {
+        day: 8,
+        size: "steps around the longest loop (at most 1000)",
+        default_size: 300,
+        build: day8,
+    },
+    Generator {
+        day: 9,
+        size: "sequences",
+        default_size: 200,
+        build: day9,
+    },
+    Generator {
+        day: 10,
+        size: "rows and columns",
+        default_size: 140,
+        build: day10,
+    },
+    Generator {
+        day: 11,
+        size: "rows and columns",
+        default_size: 140,
+        build: day11,
+    },
+    Generator {
+        day: 13,
+        size: "patterns",
+        default_size: 100,
+        build: day13,
+    },
+    Generator {
+        day: 14,
+        size: "rows and columns",
+        default_size: 100,
+        build: day14,
+    },
+    Generator {
+        day: 15,
+        size: "steps",
+        default_size: 4000,
+        build: day15,
+    },
+    Generator {
+        day: 16,
+        size: "rows and columns",
+        default_size: 110,
+        build: day16,
+    },
+    Generator {
+        day: 17,
+        size: "rows and columns",
+        default_size: 141,
+        build: day17,
+    },
+    Generator {
+        day: 18,
+        size: "steps along the top (at most 5000)",
+        default_size: 330,
+        build: day18,
+    },
+    Generator {
+        day: 19,
+        size: "workflows (at most 10000)",
+        default_size: 550,
+        build: day19,
+    },
+];
+
+/// The generator for `day` if it has one.
+#[must_use]
+pub fn get(day: u32) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|g| g.day == day)
+}
+
+const LOWER: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const UPPER: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+// Each line ending in a newline.
+fn join(lines: &[String]) -> String {
+    lines.iter().flat_map(|l| [l.as_str(), "\n"]).collect()
+}
+
+// A width x height block of characters from `cell`.
+fn rows(
+    rng: &mut StdRng,
+    width: usize,
+    height: usize,
+    mut cell: impl FnMut(&mut StdRng) -> char,
+) -> String {
+    let mut out = String::with_capacity((width + 1) * height);
+    for _ in 0..height {
+        for _ in 0..width {
+            out.push(cell(rng));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+// One of `choices` picked according to its weight.
+fn weighted(rng: &mut StdRng, choices: &[(char, u32)]) -> char {
+    choices.choose_weighted(rng, |c| c.1).unwrap().0
+}
+
+// `len` random characters from `letters`.
+fn word(rng: &mut StdRng, len: usize, letters: &[u8]) -> String {
+    (0..len)
+        .map(|_| char::from(*letters.choose(rng).unwrap()))
+        .collect()
+}
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+// Letters mixed with digits and spelled out digits (which often overlap
+// like "twone"). Every line has at least one real digit so part 1 always
+// has an answer.
+fn day1(rng: &mut StdRng, size: usize) -> String {
+    let mut lines = vec![];
+    for _ in 0..size.max(1) {
+        let mut tokens = vec![];
+        for _ in 0..rng.gen_range(1..12) {
+            tokens.push(match rng.gen_range(0..6) {
+                0 => rng.gen_range(1..=9).to_string(),
+                1 => (*WORDS.choose(rng).unwrap()).to_string(),
+                _ => {
+                    let len = rng.gen_range(1..4);
+                    word(rng, len, LOWER)
+                }
+            });
+        }
+        let at = rng.gen_range(0..=tokens.len());
+        tokens.insert(at, rng.gen_range(1..=9).to_string());
+        lines.push(tokens.concat());
+    }
+    join(&lines)
+}
+
+// Games of a few draws each with up to 20 of each colour.
+fn day2(rng: &mut StdRng, size: usize) -> String {
+    let mut lines = vec![];
+    for id in 1..=size.max(1) {
+        let mut draws = vec![];
+        for _ in 0..rng.gen_range(1..=6) {
+            let n = rng.gen_range(1..=3);
+            let colours = ["red", "green", "blue"]
+                .choose_multiple(rng, n)
+                .copied()
+                .collect::<Vec<_>>();
+            let cubes = colours
+                .iter()
+                .map(|c| format!("{} {c}", rng.gen_range(1..=20)))
+                .collect::<Vec<_>>();
+            draws.push(cubes.join(", "));
+        }
+        lines.push(format!("Game {id}: {}", draws.join("; ")));
+    }
+    join(&lines)
+}
+
+// Gears are what part 2 is about so they're the most common symbol.
+const SYMBOLS: &[(char, u32)] = &[
+    ('*', 4),
+    ('#', 1),
+    ('+', 1),
+    ('$', 1),
+    ('/', 1),
+    ('@', 1),
+    ('%', 1),
+    ('=', 1),
+    ('&', 1),
+    ('-', 1),
+];
+
+// Part numbers packed along each row with symbols dotted in the gaps.
+fn day3(rng: &mut StdRng, size: usize) -> String {
+    let size = size.max(3);
+    let mut grid = vec![vec!['.'; size]; size];
+    for row in &mut grid {
+        let mut x = rng.gen_range(0..4);
+        loop {
+            let len = rng.gen_range(1..=3);
+            if x + len > size {
+                break;
+            }
+            row[x] = char::from(rng.gen_range(b'1'..=b'9'));
+            for c in &mut row[x + 1..x + len] {
+                *c = char::from(rng.gen_range(b'0'..=b'9'));
+            }
+            x += len + rng.gen_range(1..8);
+        }
+        for c in row.iter_mut() {
+            if *c == '.' && rng.gen_ratio(1, 8) {
+                *c = weighted(rng, SYMBOLS);
+            }
+        }
+    }
+    let lines = grid.into_iter().map(String::from_iter).collect::<Vec<_>>();
+    join(&lines)
+}
+
+// Cards with 10 winning numbers and 25 picks. Most cards win nothing so the
+// copies in part 2 stay reasonable, and no card wins past the end of the
+// table.
+fn day4(rng: &mut StdRng, size: usize) -> String {
+    let size = size.max(1);
+    let list = |v: &[u32]| {
+        v.iter()
+            .map(|n| format!("{n:>2}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let mut lines = vec![];
+    for id in 1..=size {
+        let matches = if rng.gen_ratio(4, 5) {
+            0
+        } else {
+            rng.gen_range(1..=8)
+        };
+        let matches = matches.min(size - id);
+        let mut nums = (1..100).collect::<Vec<u32>>();
+        nums.shuffle(rng);
+        let (winning, rest) = nums.split_at(10);
+        let mut picks = winning[..matches]
+            .iter()
+            .chain(&rest[..25 - matches])
+            .copied()
+            .collect::<Vec<_>>();
+        picks.shuffle(rng);
+        lines.push(format!(
+            "Card {id:>3}: {} | {}",
+            list(winning),
+            list(&picks)
+        ));
+    }
+    join(&lines)
+}
+
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+// Seeds and the seven maps between categories. Each map's source ranges
+// never overlap and everything stays below 2^32.
+fn day5(rng: &mut StdRng, size: usize) -> String {
+    const SPACE: i64 = 1 << 32;
+    let size = size.max(1);
+    let mut out = String::from("seeds:");
+    for _ in 0..5 {
+        let _ = write!(
+            out,
+            " {} {}",
+            rng.gen_range(0..SPACE),
+            rng.gen_range(1..SPACE >> 4)
+        );
+    }
+    out.push('\n');
+    for pair in CATEGORIES.windows(2) {
+        let _ = write!(out, "\n{}-to-{} map:\n", pair[0], pair[1]);
+        // Pairs of distinct cuts give ranges which can't overlap.
+        let mut cuts = BTreeSet::new();
+        while cuts.len() < 2 * size {
+            cuts.insert(rng.gen_range(0..SPACE));
+        }
+        let cuts = cuts.into_iter().collect::<Vec<_>>();
+        let mut lines = cuts
+            .chunks(2)
+            .map(|c| {
+                let len = c[1] - c[0];
+                format!("{} {} {len}", rng.gen_range(0..=SPACE - len), c[0])
+            })
+            .collect::<Vec<_>>();
+        lines.shuffle(rng);
+        out.push_str(&join(&lines));
+    }
+    out
+}
+
+// The furthest a boat can go in a race lasting `t`.
+fn furthest(t: u64) -> u64 {
+    (t / 2) * (t - t / 2)
+}
+
+// Races which can all be won including the single long race part 2 reads.
+// At most 4 so that one still fits in 64 bits.
+fn day6(rng: &mut StdRng, size: usize) -> String {
+    let races = size.clamp(1, 4);
+    let joined = |v: &[u64]| v.iter().map(u64::to_string).collect::<String>();
+    let line = |v: &[u64]| {
+        v.iter()
+            .map(|n| format!("{n:>5}"))
+            .collect::<Vec<_>>()
+            .concat()
+    };
+    loop {
+        let times = (0..races)
+            .map(|_| rng.gen_range(7_u64..100))
+            .collect::<Vec<_>>();
+        let records = times
+            .iter()
+            .map(|t| rng.gen_range(0..furthest(*t)))
+            .collect::<Vec<_>>();
+        let time = joined(&times).parse::<u64>().unwrap();
+        let record = joined(&records).parse::<u64>().unwrap();
+        if record < furthest(time) {
+            return format!("Time:    {}\nDistance:{}\n", line(&times), line(&records));
+        }
+    }
+}
+
+// Random hands and bids.
+fn day7(rng: &mut StdRng, size: usize) -> String {
+    let mut lines = vec![];
+    for _ in 0..size.max(1) {
+        let hand = word(rng, 5, b"23456789TJQKA");
+        lines.push(format!("{hand} {}", rng.gen_range(1..=1000)));
+    }
+    join(&lines)
+}
+
+// A 3 letter node name ending in one of `last` which hasn't been used yet.
+fn node(rng: &mut StdRng, used: &mut HashSet<String>, last: &[u8]) -> String {
+    loop {
+        let name = word(rng, 2, UPPER) + &word(rng, 1, last);
+        if used.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+// Six ghosts each on their own loop. A start (..A) steps onto a loop which
+// ends at a ..Z and goes round again so each ghost is at its Z every loop
+// length steps, which is what part 2 relies on. AAA's loop ends at ZZZ for
+// part 1. Both ways out of a node go to the same place so any instructions
+// work. Loops are at most 1000 long so part 2 fits in 64 bits.
+fn day8(rng: &mut StdRng, size: usize) -> String {
+    let size = size.clamp(1, 1000);
+    let middle = &UPPER[1..25];
+    let len = rng.gen_range(10..300);
+    let steps = word(rng, len, b"LR");
+
+    let mut used = HashSet::from(["AAA".to_string(), "ZZZ".to_string()]);
+    let mut nodes = vec![];
+    for ghost in 0..6 {
+        let (start, end) = if ghost == 0 {
+            ("AAA".to_string(), "ZZZ".to_string())
+        } else {
+            (node(rng, &mut used, b"A"), node(rng, &mut used, b"Z"))
+        };
+        let len = rng.gen_range(size.div_ceil(2)..=size);
+        let mut cycle = (1..len)
+            .map(|_| node(rng, &mut used, middle))
+            .collect::<Vec<_>>();
+        cycle.push(end);
+        nodes.push((start, cycle[0].clone()));
+        for (i, n) in cycle.iter().enumerate() {
+            nodes.push((n.clone(), cycle[(i + 1) % len].clone()));
+        }
+    }
+    nodes.shuffle(rng);
+    let lines = nodes
+        .iter()
+        .map(|(n, next)| format!("{n} = ({next}, {next})"))
+        .collect::<Vec<_>>();
+    format!("{steps}\n\n{}", join(&lines))
+}
+
+/// Day 8 networks small enough to simulate which break everything its
+/// generator is careful about. Left and right go anywhere so ghosts can
+/// start part way round a loop, share one, pass several Z nodes or never
+/// reach one at all. There are `size` nodes (up to 8) besides AAA, ZZZ and
+/// a couple of other starts.
+#[must_use]
+pub fn network(size: usize, seed: u64) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let len = rng.gen_range(1..=4);
+    let steps = word(&mut rng, len, b"LR");
+
+    let mut used = HashSet::from(["AAA".to_string(), "ZZZ".to_string()]);
+    let mut names = vec!["AAA".to_string(), "ZZZ".to_string()];
+    for _ in 0..rng.gen_range(0..=2) {
+        names.push(node(&mut rng, &mut used, b"A"));
+    }
+    for _ in 0..size.clamp(1, 8) {
+        let last = if rng.gen_bool(0.3) {
+            b"Z"
+        } else {
+            &UPPER[1..25]
+        };
+        names.push(node(&mut rng, &mut used, last));
+    }
+    let lines = names
+        .iter()
+        .map(|n| {
+            let left = &names[rng.gen_range(0..names.len())];
+            let right = &names[rng.gen_range(0..names.len())];
+            format!("{n} = ({left}, {right})")
+        })
+        .collect::<Vec<_>>();
+    format!("{steps}\n\n{}", join(&lines))
+}
+
+// Sequences of 21 values from polynomials of degree 6 or less so the
+// differences always end in zeros.
+fn day9(rng: &mut StdRng, size: usize) -> String {
+    let mut lines = vec![];
+    for _ in 0..size.max(1) {
+        let degree = rng.gen_range(0..=6);
+        let coeffs = (0..=degree)
+            .map(|_| rng.gen_range(-10_i64..=10))
+            .collect::<Vec<_>>();
+        let values = (0..21_i64)
+            .map(|x| coeffs.iter().fold(0, |acc, c| acc * x + c).to_string())
+            .collect::<Vec<_>>();
+        lines.push(values.join(" "));
+    }
+    join(&lines)
+}
+
+// Which way `to` is from the neighbouring `from`.
+fn heading(from: (usize, usize), to: (usize, usize)) -> char {
+    match (to.0.cmp(&from.0), to.1.cmp(&from.1)) {
+        (Ordering::Greater, _) => 'E',
+        (Ordering::Less, _) => 'W',
+        (_, Ordering::Greater) => 'S',
+        _ => 'N',
+    }
+}
+
+// One step from `from` towards `to`.
+fn toward(from: usize, to: usize) -> usize {
+    match to.cmp(&from) {
+        Ordering::Greater => from + 1,
+        Ordering::Less => from - 1,
+        Ordering::Equal => from,
+    }
+}
+
+// The pipe joining the two headings.
+fn pipe(a: char, b: char) -> char {
+    let mut ends = [a, b];
+    ends.sort_unstable();
+    match ends {
+        ['N', 'S'] => '|',
+        ['E', 'W'] => '-',
+        ['E', 'N'] => 'L',
+        ['N', 'W'] => 'J',
+        ['S', 'W'] => '7',
+        _ => 'F',
+    }
+}
+
+// A loop running along the top and bottom of a band of columns, stepping up
+// and down as it goes. The top always stays above the middle row and the
+// bottom below it so it's a simple loop. Everything else is pipe junk and S
+// is somewhere on the loop with only ground beside it off the loop so
+// there's no doubt which way it goes.
+fn day10(rng: &mut StdRng, size: usize) -> String {
+    let size = size.max(8);
+    let mid = size / 2;
+    // Columns 1..size-1 leaving a margin all round.
+    let cols = size - 2;
+    let mut top = vec![rng.gen_range(1..mid)];
+    let mut bottom = vec![rng.gen_range(mid + 1..size - 1)];
+    for c in 1..cols {
+        let t = if rng.gen_ratio(1, 3) {
+            rng.gen_range(1..mid)
+        } else {
+            top[c - 1]
+        };
+        let b = if rng.gen_ratio(1, 3) {
+            rng.gen_range(mid + 1..size - 1)
+        } else {
+            bottom[c - 1]
+        };
+        top.push(t);
+        bottom.push(b);
+    }
+    // Steps happen in the column being moved into so one in the last column
+    // on top (or the first on the bottom) would double back along the side.
+    top[cols - 1] = top[cols - 2];
+    bottom[0] = bottom[1];
+
+    let mut corners = vec![(1, top[0])];
+    for c in 1..cols {
+        if top[c] != top[c - 1] {
+            corners.push((c + 1, top[c - 1]));
+            corners.push((c + 1, top[c]));
+        }
+    }
+    corners.push((cols, top[cols - 1]));
+    corners.push((cols, bottom[cols - 1]));
+    for c in (0..cols - 1).rev() {
+        if bottom[c] != bottom[c + 1] {
+            corners.push((c + 1, bottom[c + 1]));
+            corners.push((c + 1, bottom[c]));
+        }
+    }
+    corners.push((1, bottom[0]));
+
+    // Every tile along the way between each corner and the next.
+    let mut tiles = vec![];
+    for (i, from) in corners.iter().enumerate() {
+        let to = corners[(i + 1) % corners.len()];
+        let mut cur = *from;
+        while cur != to {
+            tiles.push(cur);
+            cur = (toward(cur.0, to.0), toward(cur.1, to.1));
+        }
+    }
+
+    let junk = [
+        ('.', 6),
+        ('|', 2),
+        ('-', 2),
+        ('L', 1),
+        ('J', 1),
+        ('7', 1),
+        ('F', 1),
+    ];
+    let mut grid = (0..size)
+        .map(|_| (0..size).map(|_| weighted(rng, &junk)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let n = tiles.len();
+    for (i, t) in tiles.iter().enumerate() {
+        let prev = heading(*t, tiles[(i + n - 1) % n]);
+        let next = heading(*t, tiles[(i + 1) % n]);
+        grid[t.1][t.0] = pipe(prev, next);
+    }
+    let start = tiles[rng.gen_range(0..n)];
+    grid[start.1][start.0] = 'S';
+    let on_loop = tiles.iter().copied().collect::<HashSet<_>>();
+    for n in [
+        (start.0 - 1, start.1),
+        (start.0 + 1, start.1),
+        (start.0, start.1 - 1),
+        (start.0, start.1 + 1),
+    ] {
+        if !on_loop.contains(&n) {
+            grid[n.1][n.0] = '.';
+        }
+    }
+    let lines = grid.into_iter().map(String::from_iter).collect::<Vec<_>>();
+    join(&lines)
+}
+
+// Galaxies sprinkled around with some rows and columns left empty to
+// expand.
+fn day11(rng: &mut StdRng, size: usize) -> String {
+    let size = size.max(2);
+    let empty_rows = (0..size).map(|_| rng.gen_ratio(1, 10)).collect::<Vec<_>>();
+    let empty_cols = (0..size).map(|_| rng.gen_ratio(1, 10)).collect::<Vec<_>>();
+    let mut lines = vec![];
+    for empty_row in empty_rows {
+        let mut line = String::with_capacity(size);
+        for empty_col in &empty_cols {
+            line.push(if !empty_row && !empty_col && rng.gen_ratio(1, 50) {
+                '#'
+            } else {
+                '.'
+            });
+        }
+        lines.push(line);
+    }
+    join(&lines)
+}
+
+// How many cells differ across a mirror between rows `line - 1` and `line`.
+fn mismatches(p: &[Vec<bool>], line: usize) -> usize {
+    (0..line.min(p.len() - line))
+        .map(|i| {
+            p[line - 1 - i]
+                .iter()
+                .zip(&p[line + i])
+                .filter(|(a, b)| a != b)
+                .count()
+        })
+        .sum()
+}
+
+fn transpose(p: &[Vec<bool>]) -> Vec<Vec<bool>> {
+    (0..p[0].len())
+        .map(|x| p.iter().map(|r| r[x]).collect())
+        .collect()
+}
+
+// A pattern with exactly one perfect reflection (for part 1) and exactly one
+// which needs a single smudge fixed (for part 2).
+//
+// Cells are copied so it reflects across both a column and a row. Then one
+// cell the column reflection doesn't reach gets flipped which leaves that
+// one perfect and the row one off by a smudge. Anything which happens to
+// reflect some other way as well is thrown away.
+fn mirror_pattern(rng: &mut StdRng) -> Vec<Vec<bool>> {
+    // The far side of a line copies its reflection.
+    let fold = |v: usize, line: usize| {
+        if v >= line && v < 2 * line {
+            2 * line - 1 - v
+        } else {
+            v
+        }
+    };
+    loop {
+        let (width, height) = (rng.gen_range(5..=17), rng.gen_range(5..=17));
+        let col = rng.gen_range(1..width);
+        let row = rng.gen_range(1..height);
+        // The column reflection has to leave something out for the smudge.
+        if 2 * col == width {
+            continue;
+        }
+        let cells = (0..height)
+            .map(|_| (0..width).map(|_| rng.gen()).collect::<Vec<bool>>())
+            .collect::<Vec<_>>();
+        let mut p = (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| cells[fold(y, row)][fold(x, col)])
+                    .collect()
+            })
+            .collect::<Vec<Vec<bool>>>();
+
+        let reach = col.min(width - col);
+        let outside = (0..width)
+            .filter(|x| *x + reach < col || *x >= col + reach)
+            .collect::<Vec<_>>();
+        let x = *outside.choose(rng).unwrap();
+        let rows = row.min(height - row);
+        let y = rng.gen_range(row - rows..row + rows);
+        p[y][x] = !p[y][x];
+
+        let t = transpose(&p);
+        let counts = (1..height)
+            .map(|l| mismatches(&p, l))
+            .chain((1..width).map(|l| mismatches(&t, l)))
+            .collect::<Vec<_>>();
+        let perfect = counts.iter().filter(|c| **c == 0).count();
+        let smudged = counts.iter().filter(|c| **c == 1).count();
+        if perfect == 1 && smudged == 1 {
+            return if rng.gen() { p } else { t };
+        }
+    }
+}
+
+// Mirror patterns which are guaranteed to have a reflection both with and
+// without fixing a smudge.
+fn day13(rng: &mut StdRng, size: usize) -> String {
+    let patterns = (0..size.max(1))
+        .map(|_| {
+            let lines = mirror_pattern(rng)
+                .iter()
+                .map(|r| r.iter().map(|b| if *b { '#' } else { '.' }).collect())
+                .collect::<Vec<String>>();
+            join(&lines)
+        })
+        .collect::<Vec<_>>();
+    patterns.join("\n")
+}
+
+// A platform of round and cube rocks.
+fn day14(rng: &mut StdRng, size: usize) -> String {
+    let size = size.max(1);
+    rows(rng, size, size, |rng| {
+        weighted(rng, &[('O', 4), ('#', 3), ('.', 13)])
+    })
+}
+
+// Steps adding and removing lenses. The labels come from a pool small
+// enough that they keep coming back.
+fn day15(rng: &mut StdRng, size: usize) -> String {
+    let size = size.max(1);
+    let labels = (0..=size / 4)
+        .map(|_| {
+            let len = rng.gen_range(2..=6);
+            word(rng, len, LOWER)
+        })
+        .collect::<Vec<_>>();
+    let mut steps = vec![];
+    for _ in 0..size {
+        let label = labels.choose(rng).unwrap();
+        steps.push(if rng.gen_ratio(1, 3) {
+            format!("{label}-")
+        } else {
+            format!("{label}={}", rng.gen_range(1..=9))
+        });
+    }
+    steps.join(",") + "\n"
+}
+
+// A contraption which is mostly empty space.
+fn day16(rng: &mut StdRng, size: usize) -> String {
+    let size = size.max(1);
+    rows(rng, size, size, |rng| {
+        weighted(rng, &[('.', 36), ('/', 1), ('\\', 1), ('|', 1), ('-', 1)])
+    })
+}
+
+// Heat loss digits. At least 5 across so the ultra crucible can make it.
+fn day17(rng: &mut StdRng, size: usize) -> String {
+    let size = size.max(5);
+    rows(rng, size, size, |rng| {
+        char::from(rng.gen_range(b'1'..=b'9'))
+    })
+}
+
+// The edges of a skyline with `steps` steps along the top as a direction and
+// length for each, starting from the top left going right. Its columns are
+// up to `width` wide and the top stays within `height` of the highest point.
+fn skyline(rng: &mut StdRng, steps: usize, width: u64, height: u64) -> Vec<(char, u64)> {
+    let widths = (0..=steps)
+        .map(|_| rng.gen_range(2..=width))
+        .collect::<Vec<_>>();
+    let mut tops = vec![rng.gen_range(0..=height)];
+    for s in 0..steps {
+        // Anything but the same height again.
+        let mut t = rng.gen_range(0..height);
+        if t >= tops[s] {
+            t += 1;
+        }
+        tops.push(t);
+    }
+    let bottom = height + rng.gen_range(2..=height.max(2));
+
+    let mut edges = vec![('R', widths[0])];
+    for (t, w) in tops.windows(2).zip(&widths[1..]) {
+        edges.push(if t[1] > t[0] {
+            ('D', t[1] - t[0])
+        } else {
+            ('U', t[0] - t[1])
+        });
+        edges.push(('R', *w));
+    }
+    edges.push(('D', bottom - tops[steps]));
+    edges.push(('L', widths.iter().sum()));
+    edges.push(('U', bottom - tops[0]));
+    edges
+}
+
+// Dig plans where both the part 1 steps and the part 2 colour codes trace a
+// skyline (a flat bottom with the top stepping up and down along the way).
+// That's always a simple loop and both have the same number of edges so
+// they share the lines. Part 2's edges all fit in 5 hex digits.
+fn day18(rng: &mut StdRng, size: usize) -> String {
+    let steps = size.clamp(1, 5000);
+    let small = skyline(rng, steps, 6, 10);
+    let big = skyline(rng, steps, 0xF_FFFF / (steps as u64 + 1), 0x7_FFFF);
+    let lines = small
+        .iter()
+        .zip(&big)
+        .map(|((d, n), (code, len))| format!("{d} {n} (#{len:05x}{})", "RDLU".find(*code).unwrap()))
+        .collect::<Vec<_>>();
+    join(&lines)
+}
+
+// Where a workflow rule sends parts. `None` is a free choice of accept or
+// reject.
+fn target(rng: &mut StdRng, names: &[String], dest: Option<usize>) -> String {
+    match dest {
+        Some(w) => names[w].clone(),
+        None if rng.gen() => "A".to_string(),
+        None => "R".to_string(),
+    }
+}
+
+/// Day 19 workflows and parts like its generator makes but with ratings (and
+/// the values rules test them against) only going up to `max_rating`
+/// instead of `4000`. Small enough and every possible part can be tried.
+#[must_use]
+pub fn workflows(size: usize, seed: u64, max_rating: u32) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    aplenty(&mut rng, size, max_rating.max(1))
+}
+
+fn day19(rng: &mut StdRng, size: usize) -> String {
+    aplenty(rng, size, 4000)
+}
+
+// Workflows which form a tree from `in`. Each one is sent to from exactly
+// one rule of an earlier one so there are no cycles and part 2 only reaches
+// each once. There are half as many parts as workflows.
+fn aplenty(rng: &mut StdRng, size: usize, max: u32) -> String {
+    let size = size.clamp(1, 10_000);
+    let mut used = HashSet::from(["in".to_string()]);
+    let mut names = vec!["in".to_string()];
+    while names.len() < size {
+        let len = rng.gen_range(2..=3);
+        let name = word(rng, len, LOWER);
+        if used.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+
+    // A destination for every rule plus the fallback at the end. Each
+    // workflow takes a free one from a workflow before it.
+    let mut dests = (0..size)
+        .map(|_| vec![None; rng.gen_range(2..=5)])
+        .collect::<Vec<Vec<Option<usize>>>>();
+    let mut free = (0..dests[0].len()).map(|d| (0, d)).collect::<Vec<_>>();
+    for w in 1..size {
+        let (from, d) = free.swap_remove(rng.gen_range(0..free.len()));
+        dests[from][d] = Some(w);
+        free.extend((0..dests[w].len()).map(|d| (w, d)));
+    }
+
+    let mut workflows = vec![];
+    for (name, ds) in names.iter().zip(&dests) {
+        let (fallback, ds) = ds.split_last().unwrap();
+        let mut rules = vec![];
+        for d in ds {
+            let category = char::from(*b"xmas".choose(rng).unwrap());
+            let op = if rng.gen() { '<' } else { '>' };
+            let value = rng.gen_range(1..=max);
+            rules.push(format!("{category}{op}{value}:{}", target(rng, &names, *d)));
+        }
+        rules.push(target(rng, &names, *fallback));
+        workflows.push(format!("{name}{{{}}}", rules.join(",")));
+    }
+    workflows.shuffle(rng);
+
+    let parts = (0..=size / 2)
+        .map(|_| {
+            let [x, m, a, s] = [0; 4].map(|_| rng.gen_range(1..=max));
+            format!("{{x={x},m={m},a={a},s={s}}}")
+        })
+        .collect::<Vec<_>>();
+    format!("{}\n{}", join(&workflows), join(&parts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::{self, Options};
+
+    #[test]
+    fn seeded() {
+        for g in &GENERATORS {
+            assert_eq!(g.generate(10, 7), g.generate(10, 7), "day{}", g.day);
+        }
+        let g = get(17).unwrap();
+        assert_ne!(g.generate(10, 1), g.generate(10, 2));
+    }
+
+    // Every generated input has to be one its day can solve, including the
+    // smallest.
+    #[test]
+    fn solvable() {
+        for g in &GENERATORS {
+            let solver = days::get(g.day).unwrap().solver(&Options::default());
+            for (size, seed) in [(0, 0), (1, 1), (20, 2), (20, 3), (50, 4)] {
+                let text = g.generate(size, seed);
+                if let Err(e) = solver.run_parts(&text, &[1, 2]) {
+                    panic!("day{} size {size} seed {seed}: {e:#}\n{text}", g.day);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,3 @@
+//! Pieces of the aoc runner shared between the binary and the benchmarks.
+pub mod days;
+pub mod generate;
@@ -0,0 +1,239 @@
+//! Runner for every advent day from a single binary.
+use aoc_common::output::{self, Format, Record};
+use aoc_common::parse;
+use aoc_common::render::{self, RenderArgs};
+use aoc_common::trace::{self, TraceArgs};
+use clap::{Parser, Subcommand};
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+mod scaffold;
+mod verify;
+
+use aoc::days::{self, Day, Options, DAYS};
+use aoc::generate;
+
+#[derive(Parser)]
+#[command(author, version, about)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one day (or all of them) and print a table of answers and timings.
+    Run(Box<RunArgs>),
+    /// List every day and whether it's implemented.
+    List,
+    /// Check every day against the recorded answers and exit non-zero on
+    /// any mismatch.
+    Verify(VerifyArgs),
+    /// Create a new day crate from the template and add it to the workspace
+    /// and the runner.
+    New(NewArgs),
+    /// Write a random but valid input for a day to use for benchmarks and
+    /// testing.
+    Gen(GenArgs),
+}
+
+#[derive(clap::Args)]
+struct GenArgs {
+    /// Day to generate an input for.
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..=25))]
+    day: u32,
+
+    /// How big to make it. What this counts depends on the day (it's
+    /// printed to stderr). Defaults to about the size of a real input.
+    #[arg(long)]
+    size: Option<usize>,
+
+    /// Seed for the random generator. The same day, size and seed always
+    /// give the same input. A random one is used (and printed to stderr)
+    /// when not given.
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Write the input here instead of stdout.
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(clap::Args)]
+struct NewArgs {
+    /// Day to create.
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..=25))]
+    day: u32,
+
+    /// Puzzle year (used in the doc header and puzzle link).
+    #[arg(long)]
+    year: u32,
+
+    /// Puzzle title. Defaults to the one in the registry.
+    #[arg(long)]
+    title: Option<String>,
+
+    /// Regenerate the crate even if it already exists. Example files are
+    /// kept.
+    #[arg(long, default_value_t = false)]
+    force: bool,
+}
+
+#[derive(clap::Args)]
+struct VerifyArgs {
+    /// Answers file to check against. Defaults to answers.toml at the top
+    /// of the workspace.
+    #[arg(long)]
+    answers: Option<PathBuf>,
+
+    /// Only verify this day.
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..=25))]
+    day: Option<u32>,
+}
+
+#[derive(clap::Args)]
+struct RunArgs {
+    /// Day to run.
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..=25), required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u32>,
+
+    /// Run every implemented day.
+    #[arg(long, default_value_t = false)]
+    all: bool,
+
+    /// Only run this part. Both run by default.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Input file to load. Relative paths resolve against each day's
+    /// directory (or $`AOC_INPUT_DIR`/dayN when set). Use - to read from
+    /// stdin.
+    #[arg(long, alias = "filename", default_value_t = String::from("input.txt"))]
+    input: String,
+
+    /// How to print the answers.
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    #[command(flatten, next_help_heading = "Logging")]
+    trace: TraceArgs,
+
+    #[command(flatten, next_help_heading = "Visualization")]
+    render: RenderArgs,
+
+    #[command(flatten, next_help_heading = "Day 1")]
+    day1: day1::Options,
+
+    #[command(flatten, next_help_heading = "Day 2")]
+    day2: day2::Options,
+
+    #[command(flatten, next_help_heading = "Day 18")]
+    day18: day18::Options,
+}
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+    let args: Args = Args::parse();
+
+    match args.command {
+        Command::Run(run) => {
+            trace::init(&run.trace)?;
+            render::init(&run.render)?;
+            run_days(&run)
+        }
+        Command::List => {
+            list();
+            Ok(())
+        }
+        Command::Verify(v) => {
+            verify::verify(&v.answers.unwrap_or_else(verify::default_path), v.day)
+        }
+        Command::New(n) => scaffold::new_day(
+            &scaffold::default_root(),
+            n.day,
+            n.year,
+            n.title.as_deref(),
+            n.force,
+        ),
+        Command::Gen(g) => generate(&g),
+    }
+}
+
+fn generate(args: &GenArgs) -> Result<()> {
+    let g = generate::get(args.day).ok_or_else(|| eyre!("no generator for day {}", args.day))?;
+    let size = args.size.unwrap_or(g.default_size);
+    let seed = args.seed.unwrap_or_else(rand::random);
+    eprintln!("day{}: {size} {} from seed {seed}", g.day, g.size);
+    let text = g.generate(size, seed);
+    match &args.output {
+        Some(path) => {
+            fs::write(path, text).wrap_err_with(|| format!("can't write {}", path.display()))
+        }
+        None => Ok(io::stdout().write_all(text.as_bytes())?),
+    }
+}
+
+fn list() {
+    println!("{:>3}  {:<5}  Title", "Day", "Done");
+    for d in &DAYS {
+        let done = if d.implemented { "yes" } else { "no" };
+        println!("{:>3}  {done:<5}  {}", d.number, d.title);
+    }
+}
+
+fn run_days(args: &RunArgs) -> Result<()> {
+    let opts = Options {
+        day1: args.day1.clone(),
+        day2: args.day2.clone(),
+        day18: args.day18.clone(),
+    };
+    let parts = match args.part {
+        Some(p) => vec![p],
+        None => vec![1, 2],
+    };
+
+    // A single day just fails outright. For all of them keep going so one
+    // missing input doesn't hide the rest and report at the end.
+    if let Some(day) = args.day {
+        let d = days::get(day).ok_or_else(|| eyre!("no such day {day}"))?;
+        return output::print(args.format, &run_day(d, &args.input, &parts, &opts)?);
+    }
+
+    let mut records = vec![];
+    let mut failed = 0;
+    for d in DAYS.iter().filter(|d| d.implemented) {
+        match run_day(d, &args.input, &parts, &opts) {
+            Ok(r) => records.extend(r),
+            Err(e) => {
+                failed += 1;
+                records.push(Record {
+                    day: d.number,
+                    input: d.input(&args.input).to_string(),
+                    error: Some(format!("{e:#}")),
+                    ..Record::default()
+                });
+            }
+        }
+    }
+    output::print(args.format, &records)?;
+    if failed > 0 {
+        return Err(eyre!("{failed} day(s) failed"));
+    }
+    Ok(())
+}
+
+fn run_day(d: &Day, filename: &str, parts: &[u8], opts: &Options) -> Result<Vec<Record>> {
+    let input = d.input(filename);
+    let text = input.string()?;
+    let (parse, answers) = d
+        .solver(opts)
+        .run_parts(&text, parts)
+        .map_err(|e| parse::with_file(e, &input.to_string()))
+        .wrap_err_with(|| d.name())?;
+    Ok(answers
+        .iter()
+        .map(|a| Record::new(d.number, &input.to_string(), parse, a))
+        .collect())
+}
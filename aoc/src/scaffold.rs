@@ -0,0 +1,244 @@
+//! Generate a new day crate from the template in aoc/template.
+//!
+//! Besides writing the crate this adds it to the workspace members, the
+//! runner's dependencies and the registry in days.rs so `aoc run` can find
+//! it straight away.
+use aoc::days;
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CARGO_TOML: &str = include_str!("../template/Cargo.toml.tmpl");
+const LIB_RS: &str = include_str!("../template/lib.rs.tmpl");
+const MAIN_RS: &str = include_str!("../template/main.rs.tmpl");
+
+/// Top of the workspace this binary was built from.
+pub fn default_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap_or(Path::new("."))
+        .to_path_buf()
+}
+
+/// Create dayN under `root` and hook it up everywhere it needs to be.
+///
+/// An existing day is only replaced with `force` and even then any example
+/// files already there are kept.
+pub fn new_day(root: &Path, day: u32, year: u32, title: Option<&str>, force: bool) -> Result<()> {
+    let d = days::get(day).ok_or_else(|| eyre!("no such day {day}"))?;
+    let dir = root.join(d.name());
+    if dir.exists() && !force {
+        return Err(eyre!(
+            "{} already exists (use --force to regenerate it)",
+            dir.display()
+        ));
+    }
+
+    let title = title.unwrap_or(d.title);
+    let doc = if title.is_empty() {
+        "Not solved yet.".to_string()
+    } else {
+        format!("{title}.")
+    };
+    let vars = [
+        ("day", day.to_string()),
+        ("year", year.to_string()),
+        ("title", doc),
+    ];
+
+    fs::create_dir_all(dir.join("src"))?;
+    write(&dir.join("Cargo.toml"), &render(CARGO_TOML, &vars))?;
+    write(&dir.join("src/lib.rs"), &render(LIB_RS, &vars))?;
+    write(&dir.join("src/main.rs"), &render(MAIN_RS, &vars))?;
+    let example = dir.join("example.txt");
+    if !example.exists() {
+        write(&example, "")?;
+    }
+
+    update(&root.join("Cargo.toml"), |s| add_member(s, day))?;
+    update(&root.join("aoc/Cargo.toml"), |s| add_dependency(s, day))?;
+    update(&root.join("aoc/src/days.rs"), |s| register(s, day, title))?;
+    println!("created {}", dir.display());
+    Ok(())
+}
+
+fn write(path: &Path, text: &str) -> Result<()> {
+    fs::write(path, text).wrap_err_with(|| format!("can't write {}", path.display()))
+}
+
+fn update(path: &Path, f: impl Fn(&str) -> String) -> Result<()> {
+    let text =
+        fs::read_to_string(path).wrap_err_with(|| format!("can't read {}", path.display()))?;
+    let new = f(&text);
+    if new != text {
+        write(path, &new)?;
+    }
+    Ok(())
+}
+
+// Replace every {{name}} in the template with its value.
+fn render(template: &str, vars: &[(&str, String)]) -> String {
+    vars.iter().fold(template.to_string(), |t, (k, v)| {
+        t.replace(&format!("{{{{{k}}}}}"), v)
+    })
+}
+
+// Insert `line` into each run of day lines so they stay ordered by day.
+// `day_of` returns which day a line is for (if it's one of them at all).
+// Runs are separated by anything that isn't a day line so both the members
+// and default-members lists get updated.
+fn insert_sorted(text: &str, day: u32, line: &str, day_of: impl Fn(&str) -> Option<u32>) -> String {
+    let lines = text.lines().collect::<Vec<_>>();
+    let mut out = vec![];
+    let mut i = 0;
+    while i < lines.len() {
+        if day_of(lines[i]).is_none() {
+            out.push(lines[i].to_string());
+            i += 1;
+            continue;
+        }
+        let mut run = vec![];
+        while i < lines.len() {
+            match day_of(lines[i]) {
+                Some(d) => run.push((d, lines[i].to_string())),
+                None => break,
+            }
+            i += 1;
+        }
+        if run.iter().all(|r| r.0 != day) {
+            let pos = run.iter().position(|r| r.0 > day).unwrap_or(run.len());
+            run.insert(pos, (day, line.to_string()));
+        }
+        out.extend(run.into_iter().map(|r| r.1));
+    }
+    let mut ret = out.join("\n");
+    if text.ends_with('\n') {
+        ret.push('\n');
+    }
+    ret
+}
+
+fn day_number(s: &str) -> Option<u32> {
+    s.chars()
+        .take_while(char::is_ascii_digit)
+        .collect::<String>()
+        .parse()
+        .ok()
+}
+
+// Workspace members look like `    "./day12",`.
+fn add_member(manifest: &str, day: u32) -> String {
+    insert_sorted(manifest, day, &format!("    \"./day{day}\","), |l| {
+        l.trim()
+            .strip_prefix("\"./day")
+            .filter(|r| r.ends_with("\","))
+            .and_then(day_number)
+    })
+}
+
+// Runner dependencies look like `day12 = { path = "../day12" }`.
+fn add_dependency(manifest: &str, day: u32) -> String {
+    insert_sorted(
+        manifest,
+        day,
+        &format!("day{day} = {{ path = \"../day{day}\" }}"),
+        |l| {
+            l.strip_prefix("day")
+                .filter(|r| r.contains("path = \"../day"))
+                .and_then(day_number)
+        },
+    )
+}
+
+// Make sure the registry builds the day and carries its title.
+fn register(days_rs: &str, day: u32, title: &str) -> String {
+    let arm = format!("{day} => Box::new(day{day}::Day{day}");
+    let mut lines = days_rs.lines().map(String::from).collect::<Vec<_>>();
+    let indent = |l: &str| l[..l.len() - l.trim_start().len()].to_string();
+
+    if !days_rs.contains(&arm) {
+        // Keep the match arms in day order with the catch all last.
+        let arm_day = |l: &str| {
+            let l = l.trim_start();
+            if l.starts_with("_ => unreachable!") {
+                return Some(u32::MAX);
+            }
+            l.split_once(" => Box::new(")
+                .and_then(|(n, _)| n.parse::<u32>().ok())
+        };
+        if let Some(pos) = lines
+            .iter()
+            .position(|l| arm_day(l).is_some_and(|d| d > day))
+        {
+            let line = format!("{}{arm}),", indent(&lines[pos]));
+            lines.insert(pos, line);
+        }
+    }
+
+    if !title.is_empty() {
+        let entry = format!("day({day}, \"");
+        for l in &mut lines {
+            if let Some(rest) = l.trim_start().strip_prefix(&entry) {
+                let tail = rest.split_once("\", ").map_or("false),", |t| t.1);
+                *l = format!("{}{entry}{title}\", {tail}", indent(l));
+            }
+        }
+    }
+
+    let mut ret = lines.join("\n");
+    if days_rs.ends_with('\n') {
+        ret.push('\n');
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_template() {
+        let vars = [
+            ("day", "12".to_string()),
+            ("year", "2023".to_string()),
+            ("title", "Hot springs.".to_string()),
+        ];
+        let lib = render(LIB_RS, &vars);
+        assert!(lib.starts_with("//! day12 advent 2023\n"));
+        assert!(lib.contains("/// Hot springs.\n"));
+        assert!(lib.contains("pub struct Day12;"));
+        assert!(!lib.contains("{{"));
+        assert!(render(CARGO_TOML, &vars).contains("name = \"day12\""));
+    }
+
+    #[test]
+    fn members_stay_sorted() {
+        let manifest = "[workspace]\nmembers = [\n    \"./aoc\",\n    \"./day1\",\n    \"./day3\",\n]\ndefault-members = [\n    \"./day1\",\n    \"./day3\",\n]\n";
+        let new = add_member(manifest, 2);
+        assert_eq!(new.matches("\"./day2\",").count(), 2);
+        assert!(new.contains("    \"./day1\",\n    \"./day2\",\n    \"./day3\",\n]"));
+        // Already there is left alone.
+        assert_eq!(add_member(&new, 2), new);
+    }
+
+    #[test]
+    fn dependency_added() {
+        let manifest = "[dependencies]\nclap = { workspace = true }\nday1 = { path = \"../day1\" }\nday10 = { path = \"../day10\" }\n";
+        let new = add_dependency(manifest, 9);
+        assert!(new.ends_with(
+            "day1 = { path = \"../day1\" }\nday9 = { path = \"../day9\" }\nday10 = { path = \"../day10\" }\n"
+        ));
+    }
+
+    #[test]
+    fn registry_updated() {
+        let src = "        match self.number {\n            1 => Box::new(day1::Day1),\n            3 => Box::new(day3::Day3),\n            _ => unreachable!(\"no\"),\n        }\n    day(1, \"\", true),\n    day(2, \"\", false),\n";
+        let new = register(src, 2, "Cube conundrum");
+        assert!(new.contains("Day1),\n            2 => Box::new(day2::Day2),\n            3 =>"));
+        assert!(register(src, 4, "")
+            .contains("Day3),\n            4 => Box::new(day4::Day4),\n            _ =>"));
+        assert!(new.contains("    day(2, \"Cube conundrum\", false),\n"));
+        assert!(new.contains("    day(1, \"\", true),\n"));
+        assert_eq!(register(&new, 2, "Cube conundrum"), new);
+    }
+}
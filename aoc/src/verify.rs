@@ -0,0 +1,171 @@
+//! Check every day against the recorded answers in answers.toml.
+use aoc::days::{self, Options};
+use aoc_common::output::{self, Align};
+use aoc_common::{parse, Input};
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Default location of the answers file (top of the workspace).
+pub fn default_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join("answers.toml")
+}
+
+// An answer can be written as a plain integer or a string for anything that
+// isn't a number.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum Expected {
+    Int(i64),
+    Str(String),
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Int(i) => write!(f, "{i}"),
+            Expected::Str(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Parts {
+    part1: Option<Expected>,
+    part2: Option<Expected>,
+}
+
+// dayN -> input filename -> answers
+type Answers = BTreeMap<String, BTreeMap<String, Parts>>;
+
+/// Run every recorded answer in `path` (optionally only for one day) and
+/// print a table of the results.
+///
+/// Inputs which don't exist are skipped so private puzzle inputs can be
+/// recorded without breaking verification for everyone else.
+pub fn verify(path: &Path, only: Option<u32>) -> Result<()> {
+    let (rows, failed) = check(path, only)?;
+    output::table(
+        &mut io::stdout().lock(),
+        &[
+            ("Day", Align::Right),
+            ("Part", Align::Right),
+            ("Input", Align::Left),
+            ("Expected", Align::Left),
+            ("Got", Align::Left),
+            ("Status", Align::Left),
+        ],
+        &rows,
+    )?;
+
+    if failed > 0 {
+        return Err(eyre!("{failed} answer(s) didn't match"));
+    }
+    Ok(())
+}
+
+// Run everything for verify returning a row per part along with how many
+// didn't match.
+fn check(path: &Path, only: Option<u32>) -> Result<(Vec<Vec<String>>, usize)> {
+    let text =
+        fs::read_to_string(path).wrap_err_with(|| format!("can't read {}", path.display()))?;
+    let answers: Answers =
+        toml::from_str(&text).wrap_err_with(|| format!("can't parse {}", path.display()))?;
+
+    // Keys sort as strings (day10 before day2) so put them in day order.
+    let mut entries = vec![];
+    for (name, inputs) in &answers {
+        let day = name
+            .strip_prefix("day")
+            .and_then(|n| n.parse::<u32>().ok())
+            .and_then(days::get)
+            .ok_or_else(|| eyre!("{}: unknown day {name}", path.display()))?;
+        if only.is_none_or(|d| d == day.number) {
+            entries.push((day, inputs));
+        }
+    }
+    entries.sort_by_key(|e| e.0.number);
+
+    let opts = Options::default();
+    let mut rows = vec![];
+    let mut failed = 0;
+    for (day, inputs) in entries {
+        for (filename, parts) in inputs {
+            let expected = [(1, &parts.part1), (2, &parts.part2)]
+                .into_iter()
+                .filter_map(|(p, e)| e.as_ref().map(|e| (p, e.to_string())))
+                .collect::<Vec<_>>();
+            let mut row = |part: u8, expected: &str, got: &str, status: &str| {
+                rows.push(vec![
+                    day.number.to_string(),
+                    part.to_string(),
+                    filename.clone(),
+                    expected.to_string(),
+                    got.to_string(),
+                    status.to_string(),
+                ]);
+            };
+
+            let input = day.input(filename);
+            if let Input::File(p) = &input {
+                if !p.exists() {
+                    for (part, e) in &expected {
+                        row(*part, e, "-", "skipped");
+                    }
+                    continue;
+                }
+            }
+
+            let want = expected.iter().map(|e| e.0).collect::<Vec<_>>();
+            match input
+                .string()
+                .and_then(|text| day.solver(&opts).run_parts(&text, &want))
+                .map_err(|e| parse::with_file(e, &input.to_string()))
+            {
+                Ok((_, answers)) => {
+                    for (a, (part, e)) in answers.iter().zip(&expected) {
+                        let status = if a.answer == *e {
+                            "ok"
+                        } else {
+                            failed += 1;
+                            "FAIL"
+                        };
+                        row(*part, e, &a.answer, status);
+                    }
+                }
+                Err(err) => {
+                    for (part, e) in &expected {
+                        failed += 1;
+                        row(*part, e, &format!("error: {err:#}"), "FAIL");
+                    }
+                }
+            }
+        }
+    }
+
+    Ok((rows, failed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Every example in answers.toml so cargo test covers each day's parser
+    // and parts with the same expectations aoc verify uses.
+    #[test]
+    fn answers() -> Result<()> {
+        let (rows, failed) = check(&default_path(), None)?;
+        assert!(rows.iter().any(|r| r[5] == "ok"), "no answers checked");
+        let failures = rows.iter().filter(|r| r[5] == "FAIL").collect::<Vec<_>>();
+        assert_eq!(failed, 0, "{failures:?}");
+        Ok(())
+    }
+}
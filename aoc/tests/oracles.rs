@@ -0,0 +1,77 @@
+//! Cross check the days which rely on shortcuts against their slow but
+//! obviously correct references on small generated inputs.
+//!
+//! The shortcuts all depend on something about the input (paths looping
+//! neatly, the platform settling into a cycle, the trench being a simple
+//! loop) so these make sure they hold up on inputs beyond the one real one.
+use aoc::generate;
+use aoc_common::interval::Interval;
+use aoc_common::Solution;
+use proptest::prelude::*;
+
+// Generated text for `day` parsed by `solution`.
+fn input<S: Solution>(solution: &S, size: usize, seed: u64) -> S::Input {
+    let text = generate::get(S::DAY).unwrap().generate(size, seed);
+    solution.parse(&text).unwrap()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn day8_lcm_matches_simulation(seed in any::<u64>(), size in 1_usize..8) {
+        let day = day8::Day8;
+        let network = day.parse(&generate::network(size, seed)).unwrap();
+        // A ghost is always on one of the nodes at one point in the steps so
+        // it's repeating itself within this many, and all of them together
+        // within this to the power of how many there are.
+        let states = network.conditions.len() * network.steps.len();
+        let simulated = day8::simulate(&network, &["AAA"], |n| n == "ZZZ", states);
+        prop_assert_eq!(day.part1(&network).ok(), simulated);
+        let starts = network
+            .conditions
+            .keys()
+            .filter(|n| n.ends_with('A'))
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        // The shortcut is allowed to give up when the loops don't line up
+        // neatly but mustn't give the wrong answer.
+        if let Ok(steps) = day.part2(&network) {
+            let limit = states.pow(u32::try_from(starts.len()).unwrap());
+            prop_assert_eq!(
+                Some(steps),
+                day8::simulate(&network, &starts, |n| n.ends_with('Z'), limit)
+            );
+        }
+    }
+
+    #[test]
+    fn day14_cycle_matches_spinning(seed in any::<u64>(), size in 1_usize..10, cycles in 0_usize..200) {
+        let platform = input(&day14::Day14, size, seed);
+        prop_assert_eq!(
+            day14::load_after(&platform, cycles).unwrap(),
+            day14::spin(&platform, cycles)
+        );
+    }
+
+    #[test]
+    fn day18_picks_theorem_matches_flood_fill(seed in any::<u64>(), size in 1_usize..8) {
+        let day = day18::Day18::default();
+        let plan = input(&day, size, seed);
+        let filled = day18::flood_fill(&plan.dig);
+        prop_assert_eq!(day18::lagoon(&plan.dig).unwrap(), i128::try_from(filled).unwrap());
+        prop_assert_eq!(day.part1(&plan).unwrap(), i128::try_from(filled).unwrap());
+    }
+
+    #[test]
+    fn day19_ranges_match_enumeration(seed in any::<u64>(), size in 1_usize..12) {
+        let text = generate::workflows(size, seed, 6);
+        let system = day19::Day19.parse(&text).unwrap();
+        // One past the largest value tested on each side.
+        let ratings = Interval::new(1, 8);
+        prop_assert_eq!(
+            day19::combinations(&system, ratings).unwrap(),
+            day19::enumerate(&system, ratings).unwrap()
+        );
+    }
+}
@@ -0,0 +1,700 @@
+//! day1 advent 20XX
+use aho_corasick::AhoCorasick;
+use aoc_common::output::Format;
+use aoc_common::Solution;
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Trebuchet calibration.
+#[derive(Debug, Default)]
+pub struct Day1 {
+    /// Which spelled out digits count.
+    pub options: Options,
+}
+
+/// Which spelled out digits count for part2.
+#[derive(Clone, Debug, Default, clap::Args)]
+#[group(skip)]
+pub struct Options {
+    /// Language to take spelled out digits from. Can be given more than
+    /// once. English when none are given.
+    #[arg(long = "language", value_enum)]
+    pub languages: Vec<Language>,
+
+    /// Count 0 (and zero spelled out) as a digit too.
+    #[arg(long, default_value_t = false)]
+    pub zero: bool,
+
+    /// An extra spelled out digit. Can be given more than once.
+    #[arg(long = "word", value_name = "WORD=DIGIT", value_parser = parse_word)]
+    pub words: Vec<(String, u8)>,
+
+    /// TOML file with languages, zero and words to use as well.
+    #[arg(long, value_name = "FILE")]
+    pub vocabulary: Option<PathBuf>,
+}
+
+impl Options {
+    /// The vocabulary from the file (if any) with the command line added.
+    ///
+    /// # Errors
+    /// If the vocabulary file can't be read or parsed.
+    pub fn vocabulary(&self) -> Result<Vocabulary> {
+        let mut v = match &self.vocabulary {
+            Some(path) => Vocabulary::load(path)?,
+            None => Vocabulary::default(),
+        };
+        v.languages.extend(&self.languages);
+        v.zero |= self.zero;
+        v.words.extend(self.words.iter().cloned());
+        Ok(v)
+    }
+}
+
+fn parse_word(s: &str) -> Result<(String, u8), String> {
+    let (word, digit) = s
+        .split_once('=')
+        .ok_or_else(|| format!("{s}: expected WORD=DIGIT"))?;
+    let digit = digit
+        .parse::<u8>()
+        .ok()
+        .filter(|d| *d <= 9)
+        .ok_or_else(|| format!("{s}: {digit} isn't a digit"))?;
+    Ok((word.to_string(), digit))
+}
+
+/// A language digits can be spelled out in.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    /// one, two, three...
+    English,
+    /// un, deux, trois...
+    French,
+    /// eins, zwei, drei...
+    German,
+    /// uno, dos, tres...
+    Spanish,
+}
+
+impl Language {
+    /// The words for `0` to `9` in order.
+    #[must_use]
+    pub fn words(self) -> [&'static str; 10] {
+        match self {
+            Language::English => [
+                "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+            ],
+            Language::French => [
+                "zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+            ],
+            Language::German => [
+                "null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+            ],
+            Language::Spanish => [
+                "cero", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve",
+            ],
+        }
+    }
+}
+
+/// The spelled out digits part2 looks for. As a file this is TOML like:
+///
+/// ```toml
+/// languages = ["english", "french"]
+/// zero = true
+///
+/// [words]
+/// won = 1
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Vocabulary {
+    /// Languages to take words from. English when there aren't any.
+    pub languages: Vec<Language>,
+    /// Whether 0 counts as a digit (along with its word in each language).
+    pub zero: bool,
+    /// Extra words and the digit each one means. These win over any
+    /// language's word for a different digit.
+    pub words: BTreeMap<String, u8>,
+}
+
+impl Vocabulary {
+    /// Read a vocabulary from a TOML file.
+    ///
+    /// # Errors
+    /// If the file can't be read or isn't a valid vocabulary.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text =
+            fs::read_to_string(path).wrap_err_with(|| format!("can't read {}", path.display()))?;
+        toml::from_str(&text).wrap_err_with(|| format!("can't parse {}", path.display()))
+    }
+
+    // The lowest digit allowed.
+    fn lowest(&self) -> u8 {
+        u8::from(!self.zero)
+    }
+
+    /// Every spelled out digit with what it means.
+    #[must_use]
+    pub fn spelled(&self) -> BTreeMap<String, u8> {
+        let languages = if self.languages.is_empty() {
+            &[Language::English][..]
+        } else {
+            &self.languages
+        };
+        let mut words = BTreeMap::new();
+        for l in languages {
+            for (d, w) in (0..=9).zip(l.words()) {
+                if d >= self.lowest() {
+                    words.insert(w.to_string(), d);
+                }
+            }
+        }
+        words.extend(self.words.clone());
+        words
+    }
+}
+
+/// How a digit was written.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    /// As a digit like `7`.
+    Digit,
+    /// Spelled out like `seven`.
+    Word,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Kind::Digit => "digit",
+            Kind::Word => "word",
+        })
+    }
+}
+
+/// A digit found in a line.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub struct Token {
+    /// Byte offset it starts at.
+    pub start: usize,
+    /// Byte offset just past the end.
+    pub end: usize,
+    /// The digit it means.
+    pub digit: u8,
+    /// How it was written.
+    pub kind: Kind,
+}
+
+/// The first and last digits found in a line.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Ends {
+    /// The one starting furthest left.
+    pub first: Token,
+    /// The one starting furthest right.
+    pub last: Token,
+}
+
+impl Ends {
+    /// The two digit calibration value.
+    #[must_use]
+    pub fn value(&self) -> usize {
+        usize::from(self.first.digit * 10 + self.last.digit)
+    }
+
+    // Take `t` into account. Where two start at the same place the longer
+    // one counts.
+    fn push(ends: &mut Option<Ends>, t: Token) {
+        match ends {
+            None => *ends = Some(Ends { first: t, last: t }),
+            Some(e) => {
+                if (t.start, e.first.end) < (e.first.start, t.end) {
+                    e.first = t;
+                }
+                if (t.start, t.end) > (e.last.start, e.last.end) {
+                    e.last = t;
+                }
+            }
+        }
+    }
+}
+
+// Along with the value so explanations don't have to work it out.
+impl Serialize for Ends {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let mut e = s.serialize_struct("Ends", 3)?;
+        e.serialize_field("first", &self.first)?;
+        e.serialize_field("last", &self.last)?;
+        e.serialize_field("value", &self.value())?;
+        e.end()
+    }
+}
+
+/// The first and last digits of a line for both parts.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Calibration {
+    /// Only counting digits (part1).
+    pub digits: Option<Ends>,
+    /// Counting spelled out digits too (part2).
+    pub words: Option<Ends>,
+}
+
+/// Finds digits and spelled out digits in a line.
+///
+/// Every digit and word in the vocabulary goes into a single Aho-Corasick
+/// automaton built up front so each line is scanned once, left to right,
+/// for both parts together. Overlapping matches are all reported so
+/// "twone" is a two followed by a one.
+#[derive(Clone, Debug)]
+pub struct Recognizer {
+    automaton: AhoCorasick,
+    // What each pattern in the automaton means.
+    tokens: Vec<(u8, Kind)>,
+}
+
+impl Recognizer {
+    /// Build the automaton for `vocabulary`.
+    ///
+    /// # Errors
+    /// If a word is empty or means something other than a digit.
+    pub fn new(vocabulary: &Vocabulary) -> Result<Self> {
+        let mut patterns = vec![];
+        let mut tokens = vec![];
+        for d in vocabulary.lowest()..=9 {
+            patterns.push(d.to_string());
+            tokens.push((d, Kind::Digit));
+        }
+        for (w, d) in vocabulary.spelled() {
+            if w.is_empty() || d > 9 || d < vocabulary.lowest() {
+                return Err(eyre!("{w:?} can't mean {d}"));
+            }
+            patterns.push(w);
+            tokens.push((d, Kind::Word));
+        }
+        Ok(Self {
+            automaton: AhoCorasick::new(&patterns)?,
+            tokens,
+        })
+    }
+
+    /// Every digit in `line` in the order they end. Lines don't have to be
+    /// valid UTF-8.
+    pub fn tokens<'a, L: AsRef<[u8]> + ?Sized>(
+        &'a self,
+        line: &'a L,
+    ) -> impl Iterator<Item = Token> + 'a {
+        self.automaton
+            .find_overlapping_iter(line.as_ref())
+            .map(|m| {
+                let (digit, kind) = self.tokens[m.pattern().as_usize()];
+                Token {
+                    start: m.start(),
+                    end: m.end(),
+                    digit,
+                    kind,
+                }
+            })
+    }
+
+    /// The first and last digits in `line` for both parts.
+    #[must_use]
+    pub fn calibrate<L: AsRef<[u8]> + ?Sized>(&self, line: &L) -> Calibration {
+        let mut c = Calibration::default();
+        for t in self.tokens(line) {
+            if t.kind == Kind::Digit {
+                Ends::push(&mut c.digits, t);
+            }
+            Ends::push(&mut c.words, t);
+        }
+        c
+    }
+}
+
+/// A line with no digit for one of the parts so it added nothing there.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Warning {
+    /// Line number (starting at 1).
+    pub line: u64,
+    /// Which part found nothing.
+    pub part: u8,
+}
+
+impl Warning {
+    /// Log as a warning event with the line and part as fields.
+    pub fn log(self) {
+        warn!(line = self.line, part = self.part, "no digit");
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: no digit for part {}", self.line, self.part)
+    }
+}
+
+/// Running totals for both parts.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Totals {
+    /// Lines which weren't empty.
+    pub lines: u64,
+    /// Sum of the part1 values.
+    pub part1: u128,
+    /// Sum of the part2 values.
+    pub part2: u128,
+    /// How many lines had no digit for part1 and part2.
+    pub missing: [u64; 2],
+}
+
+/// How one line was read, for `--explain`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Explanation {
+    /// Line number (starting at 1).
+    pub line: u64,
+    /// The line itself (with anything not UTF-8 replaced).
+    pub text: String,
+    /// Every digit found in the order they end.
+    pub tokens: Vec<Found>,
+    /// What part1 picked.
+    pub part1: Option<Ends>,
+    /// What part2 picked.
+    pub part2: Option<Ends>,
+}
+
+/// A digit found in a line along with how it was written there.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Found {
+    /// Where it was and what it means.
+    #[serde(flatten)]
+    pub token: Token,
+    /// The text matched.
+    pub text: String,
+}
+
+impl fmt::Display for Found {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{} {}", self.text, self.token.start, self.token.kind)
+    }
+}
+
+// Like `2@0 9@4 = 29` for the digits picked (and where) and the value.
+fn show(ends: Option<&Ends>) -> String {
+    ends.map_or_else(
+        || "-".to_string(),
+        |e| {
+            format!(
+                "{}@{} {}@{} = {}",
+                e.first.digit,
+                e.first.start,
+                e.last.digit,
+                e.last.start,
+                e.value()
+            )
+        },
+    )
+}
+
+impl Explanation {
+    /// Write this explanation in `format`. `first` says whether it's the
+    /// first one written which is needed for separators. See
+    /// [`Day1::explain`].
+    ///
+    /// # Errors
+    /// If `w` can't be written.
+    pub fn write(&self, format: Format, first: bool, w: &mut impl Write) -> Result<()> {
+        let tokens = self
+            .tokens
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        let tokens = if tokens.is_empty() {
+            "-".to_string()
+        } else {
+            tokens.join(", ")
+        };
+        match format {
+            Format::Text => {
+                if !first {
+                    writeln!(w)?;
+                }
+                writeln!(w, "line {}: {}", self.line, self.text)?;
+                writeln!(w, "  tokens: {tokens}")?;
+                writeln!(w, "  part 1: {}", show(self.part1.as_ref()))?;
+                writeln!(w, "  part 2: {}", show(self.part2.as_ref()))?;
+            }
+            Format::Json => {
+                let sep = if first { "" } else { "," };
+                writeln!(w, "{sep}{}", serde_json::to_string(self)?)?;
+            }
+            Format::Tsv => {
+                let cells = [
+                    self.line.to_string(),
+                    self.text.clone(),
+                    tokens,
+                    show(self.part1.as_ref()),
+                    show(self.part2.as_ref()),
+                ];
+                // No quoting in TSV so any tabs in a line become spaces.
+                let cells = cells.map(|c| c.replace('\t', " "));
+                writeln!(w, "{}", cells.join("\t"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Day1 {
+    /// Go through `reader` a line at a time adding up both parts. Only the
+    /// current line is ever held so this works on inputs of any size. Lines
+    /// with no digit for a part count as 0 there and are handed to `warn`.
+    ///
+    /// # Errors
+    /// If the vocabulary is bad or `reader` fails.
+    pub fn stream(&self, reader: impl BufRead, mut warn: impl FnMut(Warning)) -> Result<Totals> {
+        let r = Recognizer::new(&self.options.vocabulary()?)?;
+        let mut totals = Totals::default();
+        lines(reader, |line, text| {
+            totals.lines += 1;
+            let c = r.calibrate(text);
+            for (part, ends, sum) in [
+                (1, c.digits, &mut totals.part1),
+                (2, c.words, &mut totals.part2),
+            ] {
+                if let Some(e) = ends {
+                    // A usize always fits.
+                    *sum += e.value() as u128;
+                } else {
+                    totals.missing[usize::from(part - 1)] += 1;
+                    warn(Warning { line, part });
+                }
+            }
+            Ok(())
+        })?;
+        Ok(totals)
+    }
+
+    /// Write how every line in `reader` was read by both parts to `w` in
+    /// `format`. Like [`Day1::stream`] only the current line is held so
+    /// each one is written as soon as it's read. Text is a short block per
+    /// line, JSON an array with one object per line and TSV a row per line.
+    ///
+    /// # Errors
+    /// If the vocabulary is bad, `reader` fails or `w` can't be written.
+    pub fn explain(&self, reader: impl BufRead, format: Format, w: &mut impl Write) -> Result<()> {
+        let r = Recognizer::new(&self.options.vocabulary()?)?;
+        match format {
+            Format::Text => {}
+            Format::Json => writeln!(w, "[")?,
+            Format::Tsv => writeln!(w, "line\ttext\ttokens\tpart1\tpart2")?,
+        }
+        let mut first = true;
+        lines(reader, |line, text| {
+            let c = r.calibrate(text);
+            let e = Explanation {
+                line,
+                text: String::from_utf8_lossy(text).into_owned(),
+                tokens: r
+                    .tokens(text)
+                    .map(|token| Found {
+                        token,
+                        text: String::from_utf8_lossy(&text[token.start..token.end]).into_owned(),
+                    })
+                    .collect(),
+                part1: c.digits,
+                part2: c.words,
+            };
+            e.write(format, first, w)?;
+            first = false;
+            Ok(())
+        })?;
+        if format == Format::Json {
+            writeln!(w, "]")?;
+        }
+        Ok(())
+    }
+
+    // Both totals from `text` only logging warnings for `part`.
+    fn totals(&self, text: &str, part: u8) -> Result<Totals> {
+        self.stream(text.as_bytes(), |w| {
+            if w.part == part {
+                w.log();
+            }
+        })
+    }
+}
+
+// Call `f` with the number (starting at 1) and text of every line in
+// `reader` reusing the same buffer for each.
+fn lines(mut reader: impl BufRead, mut f: impl FnMut(u64, &[u8]) -> Result<()>) -> Result<()> {
+    let mut buf = vec![];
+    let mut line = 0;
+    loop {
+        buf.clear();
+        line += 1;
+        let n = reader
+            .read_until(b'\n', &mut buf)
+            .wrap_err_with(|| format!("can't read line {line}"))?;
+        if n == 0 {
+            return Ok(());
+        }
+        let text = buf.strip_suffix(b"\n").unwrap_or(&buf);
+        let text = text.strip_suffix(b"\r").unwrap_or(text);
+        // Skip empty lines in case the file got extra somehow.
+        if !text.is_empty() {
+            f(line, text)?;
+        }
+    }
+}
+
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+    type Input = String;
+    type Output = usize;
+
+    // Input looks like:
+    //
+    // 4nineeightseven2
+    // 1abc2
+    //
+    // For part1 just find the left most digit and right most digit and make
+    // a 2 digit number out of them. For a single digit case like:
+    //
+    // two1nine
+    //
+    // It would compute to 11 then. Sum up all the numbers.
+    //
+    // For part2 spelled out digits are now possible and again we want the
+    // left most and right most ones. So the example above instead of 11 actually
+    // becomes 29. Again sum these up.
+    //
+    // A line without any digit adds nothing (and gets a warning logged).
+    //
+    // Both parts go through stream() a line at a time so there's nothing to
+    // do here beyond holding on to the text.
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        Ok(input.to_string())
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<usize> {
+        Ok(usize::try_from(self.totals(input, 1)?.part1)?)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<usize> {
+        Ok(usize::try_from(self.totals(input, 2)?.part2)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(v: &Vocabulary, line: &str) -> (Option<usize>, Option<usize>) {
+        let c = Recognizer::new(v).unwrap().calibrate(line);
+        (c.digits.map(|e| e.value()), c.words.map(|e| e.value()))
+    }
+
+    #[test]
+    fn overlaps() {
+        let v = Vocabulary::default();
+        assert_eq!(values(&v, "twone"), (None, Some(21)));
+        assert_eq!(values(&v, "eightwo3three"), (Some(33), Some(83)));
+        assert_eq!(values(&v, "oneight"), (None, Some(18)));
+        assert_eq!(values(&v, "xyz"), (None, None));
+        let c = Recognizer::new(&v).unwrap().calibrate("a7seven");
+        let words = c.words.unwrap();
+        assert_eq!((words.first.kind, words.first.start), (Kind::Digit, 1));
+        assert_eq!((words.last.kind, words.last.start), (Kind::Word, 2));
+    }
+
+    #[test]
+    fn vocabularies() {
+        let v = toml::from_str::<Vocabulary>(
+            "languages = [\"french\", \"german\"]\nzero = true\n[words]\nuno = 4\n",
+        )
+        .unwrap();
+        assert_eq!(values(&v, "zweiunozéro"), (None, Some(20)));
+        assert_eq!(values(&v, "0neuf"), (Some(0), Some(9)));
+        // English isn't there unless asked for.
+        assert_eq!(values(&v, "one"), (None, None));
+        // At the same start the longer word wins.
+        let v = Vocabulary {
+            words: BTreeMap::from([("on".to_string(), 5)]),
+            ..Vocabulary::default()
+        };
+        assert_eq!(values(&v, "one"), (None, Some(11)));
+
+        let bad = Vocabulary {
+            words: BTreeMap::from([(String::new(), 1)]),
+            ..Vocabulary::default()
+        };
+        assert!(Recognizer::new(&bad).is_err());
+        assert!(parse_word("ten=10").is_err());
+        assert_eq!(parse_word("won=1"), Ok(("won".to_string(), 1)));
+    }
+
+    #[test]
+    fn streaming() -> Result<()> {
+        let text = b"1abc2\r\n\nxyz\nseven\xff8\n\ntwo";
+        let mut warnings = vec![];
+        let totals = Day1::default().stream(&text[..], |w| warnings.push(w))?;
+        assert_eq!(
+            totals,
+            Totals {
+                lines: 4,
+                part1: 12 + 88,
+                part2: 12 + 78 + 22,
+                missing: [2, 1],
+            }
+        );
+        assert_eq!(
+            warnings,
+            [
+                Warning { line: 3, part: 1 },
+                Warning { line: 3, part: 2 },
+                Warning { line: 6, part: 1 },
+            ]
+        );
+        assert_eq!(warnings[0].to_string(), "line 3: no digit for part 1");
+
+        // Part2 used to panic on a line like this.
+        let day = Day1::default();
+        assert_eq!(day.part2(&day.parse("xyz\none")?)?, 11);
+        Ok(())
+    }
+
+    #[test]
+    fn explaining() -> Result<()> {
+        let explain = |format| -> Result<String> {
+            let mut out = vec![];
+            Day1::default().explain(&b"xyz\n\ntwo1nine\n"[..], format, &mut out)?;
+            Ok(String::from_utf8(out)?)
+        };
+        assert_eq!(
+            explain(Format::Text)?,
+            "line 1: xyz\n  tokens: -\n  part 1: -\n  part 2: -\n\n\
+             line 3: two1nine\n  tokens: two@0 word, 1@3 digit, nine@4 word\n\
+             \x20 part 1: 1@3 1@3 = 11\n  part 2: 2@0 9@4 = 29\n"
+        );
+
+        let json = serde_json::from_str::<serde_json::Value>(&explain(Format::Json)?)?;
+        assert_eq!(json[0]["line"], 1);
+        assert_eq!(json[0]["part1"], serde_json::Value::Null);
+        assert_eq!(json[1]["part2"]["value"], 29);
+        assert_eq!(json[1]["part2"]["first"]["start"], 0);
+        assert_eq!(json[1]["tokens"][1]["kind"], "digit");
+        assert_eq!(json[1]["tokens"][2]["text"], "nine");
+
+        let tsv = explain(Format::Tsv)?;
+        assert_eq!(tsv.lines().count(), 3);
+        assert!(tsv.ends_with("\t1@3 1@3 = 11\t2@0 9@4 = 29\n"));
+        Ok(())
+    }
+}
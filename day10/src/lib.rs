@@ -0,0 +1,279 @@
+//! day10 advent 20XX
+#[allow(clippy::enum_glob_use)]
+use crate::Pipes::*;
+use aoc_common::animate::{self, Screen};
+use aoc_common::direction::Direction::{self, East, North, South, West};
+use aoc_common::geometry::{Polygon, Position};
+use aoc_common::render::{self, Image, Rgb};
+use aoc_common::{parse, trace, ParseError, Solution, Step};
+use color_eyre::eyre::{eyre, Result};
+use grid::{Grid, Location};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use tracing::{debug, enabled, Level};
+
+/// Pipe maze.
+#[derive(Debug, Default)]
+pub struct Day10;
+
+/// One tile of the maze.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Pipes {
+    /// |
+    Vertical,
+    /// -
+    Horizontal,
+    /// L
+    NEBend,
+    /// J
+    NWBend,
+    /// 7
+    SWBend,
+    /// F
+    SEBend,
+    /// .
+    #[default]
+    Ground,
+    /// S
+    Start,
+    /// Ground determined to be outside the loop.
+    Outside,
+    /// Ground determined to be inside the loop.
+    Inside,
+}
+
+/// The parsed maze and where the animal starts.
+#[derive(Clone, Debug)]
+pub struct Maze {
+    /// The tiles.
+    pub grid: Grid<Pipes>,
+    /// Location of the S tile.
+    pub start: Location,
+}
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    type Input = Maze;
+    type Output = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let lines = parse::rectangle(input)?;
+        let mut grid = Grid::<Pipes>::new(lines[0].len(), lines.len());
+
+        let mut start = None;
+        for (line_num, line) in lines.iter().enumerate() {
+            for (pos, b) in line.as_bytes().iter().enumerate() {
+                let x = isize::try_from(pos)?;
+                let y = isize::try_from(line_num)?;
+                grid.add(
+                    &Location(x, y),
+                    match b {
+                        b'.' => Ground,
+                        b'-' => Horizontal,
+                        b'|' => Vertical,
+                        b'L' => NEBend,
+                        b'J' => NWBend,
+                        b'7' => SWBend,
+                        b'F' => SEBend,
+                        b'S' => {
+                            if start.is_some() {
+                                return Err(ParseError::at_byte(
+                                    line_num + 1,
+                                    line,
+                                    pos,
+                                    "more than one start",
+                                )
+                                .into());
+                            }
+                            start = Some(Location(x, y));
+                            Start
+                        }
+                        _ => {
+                            return Err(ParseError::at_byte(
+                                line_num + 1,
+                                line,
+                                pos,
+                                "invalid pipe",
+                            )
+                            .into())
+                        }
+                    },
+                );
+            }
+        }
+        let Some(start) = start else {
+            return Err(ParseError::new(1, 1, "", "no start (S) in the maze").into());
+        };
+        Ok(Maze { grid, start })
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<usize> {
+        // The path ends back at the start so its length is the loop length + 1.
+        Ok(walk(input)?.0.len() / 2)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<usize> {
+        // Every tile on the loop is a vertex of a polygon and every tile center
+        // is a lattice point so the enclosed tiles are its interior points.
+        let (path, mut walk_grid) = walk(input)?;
+        let polygon = Polygon::from_path(path)?;
+        let cnt = polygon.interior_points();
+
+        if enabled!(Level::DEBUG) || render::enabled() || animate::enabled() {
+            mark_enclosed(&mut walk_grid, &polygon);
+            debug!("enclosed:\n{}", trace::grid(&walk_grid));
+            render::frame("day10", || Image::from_grid(&walk_grid, colour))?;
+            animate::frame("day10", || Screen::from_grid(&walk_grid, colour))?;
+        }
+        Ok(cnt.try_into()?)
+    }
+}
+
+fn allowed() -> HashMap<Pipes, HashSet<Direction>> {
+    HashMap::from([
+        (Vertical, HashSet::from([North, South])),
+        (Horizontal, HashSet::from([East, West])),
+        (NEBend, HashSet::from([North, East])),
+        (NWBend, HashSet::from([North, West])),
+        (SWBend, HashSet::from([South, West])),
+        (SEBend, HashSet::from([South, East])),
+    ])
+}
+
+// Walk the loop from the start returning the path (which ends back at the
+// start) and a grid with only the loop tiles filled in.
+fn walk(maze: &Maze) -> Result<(Vec<Location>, Grid<Pipes>)> {
+    let allowed = allowed();
+    let grid = &maze.grid;
+    let start = &maze.start;
+
+    // The start connects to any neighbor with an opening pointing back at it.
+    let mut start_valid = HashSet::new();
+    for d in Direction::ALL {
+        let Some(n) = start.step_within(d, grid) else {
+            continue;
+        };
+        if allowed
+            .get(grid.get(&n))
+            .is_some_and(|a| a.contains(&d.opposite()))
+        {
+            start_valid.insert(d);
+        }
+    }
+
+    // Exactly two neighbors have to connect or there's no telling which
+    // way the loop goes.
+    let start_pipe = allowed
+        .iter()
+        .find(|f| f.1 == &start_valid)
+        .ok_or_else(|| eyre!("start at {start} connects {start_valid:?} which isn't a pipe"))?
+        .0
+        .clone();
+    debug!("grid:\n{}", trace::grid(grid));
+    debug!(%start, ?start_valid, %start_pipe, "start");
+
+    let mut cur = start.clone();
+    let mut last = start.clone();
+    let mut cur_symbol = start_pipe;
+    let mut path = vec![start.clone()];
+    let mut walk_grid = Grid::<Pipes>::new(grid.width(), grid.height());
+    walk_grid.add(&cur, Start);
+    loop {
+        let exits = allowed
+            .get(&cur_symbol)
+            .ok_or_else(|| eyre!("loop runs off the pipes at {cur}"))?;
+        for c in exits {
+            let testloc = cur
+                .step_within(*c, grid)
+                .ok_or_else(|| eyre!("loop runs off the edge at {cur}"))?;
+            if testloc != last {
+                // Whatever's next has to lead back here (the start always
+                // does).
+                if testloc != *start
+                    && !allowed
+                        .get(grid.get(&testloc))
+                        .is_some_and(|a| a.contains(&c.opposite()))
+                {
+                    return Err(eyre!(
+                        "pipe at {cur} leads to {testloc} which doesn't connect back"
+                    ));
+                }
+                path.push(testloc.clone());
+                last = cur;
+                cur_symbol = grid.get(&testloc).clone();
+                cur = testloc;
+                walk_grid.add(&cur, cur_symbol.clone());
+                animate::frame("day10-walk", || Screen::from_grid(&walk_grid, colour))?;
+                break;
+            }
+        }
+        if cur == *start {
+            break;
+        }
+    }
+
+    debug!("loop:\n{}", trace::grid(&walk_grid));
+    Ok((path, walk_grid))
+}
+
+// Fill in every ground tile as inside or outside the loop for display.
+fn mark_enclosed(grid: &mut Grid<Pipes>, polygon: &Polygon) {
+    let ground = grid
+        .iter()
+        .filter(|f| *f.1 == Ground)
+        .map(|f| f.0)
+        .collect::<Vec<_>>();
+    for l in ground {
+        *grid.get_mut(&l) = match polygon.locate(&l) {
+            Position::Inside => Inside,
+            _ => Outside,
+        };
+    }
+}
+
+fn colour(p: &Pipes) -> Rgb {
+    match p {
+        Ground => Rgb::GREY,
+        Inside => Rgb(0x20, 0xC0, 0x20),
+        Outside => Rgb(0x10, 0x10, 0x60),
+        Start => Rgb::RED,
+        _ => Rgb::WHITE,
+    }
+}
+
+impl fmt::Display for Pipes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Vertical => write!(f, "|"),
+            Horizontal => write!(f, "-"),
+            NEBend => write!(f, "L"),
+            NWBend => write!(f, "J"),
+            SWBend => write!(f, "7"),
+            SEBend => write!(f, "F"),
+            Ground => write!(f, "."),
+            Start => write!(f, "S"),
+            Outside => write!(f, "O"),
+            Inside => write!(f, "I"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn broken_loops() -> Result<()> {
+        let day = Day10;
+        // S connects three ways.
+        let input = day.parse(".|.\n-S-\n...\n")?;
+        assert!(day.part1(&input).is_err());
+        // The loop leads into ground.
+        let input = day.parse("S-7\n|.|\nL-.\n")?;
+        assert!(day.part2(&input).is_err());
+        // And off the edge.
+        let input = day.parse("S-7\n|.|\nL-L\n")?;
+        assert!(day.part1(&input).is_err());
+        Ok(())
+    }
+}
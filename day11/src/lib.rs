@@ -0,0 +1,140 @@
+//! day11 advent 20XX
+use aoc_common::{parse, ParseError, Solution};
+use color_eyre::eyre::Result;
+use grid::Location;
+use itertools::Itertools;
+use std::collections::HashSet;
+use tracing::{debug, enabled, trace, Level};
+
+/// Cosmic expansion.
+#[derive(Debug, Default)]
+pub struct Day11;
+
+/// Galaxy locations along with the size of the original image.
+#[derive(Clone, Debug, Default)]
+pub struct Image {
+    /// Every galaxy.
+    pub locs: Vec<Location>,
+    /// Image width.
+    pub width: usize,
+    /// Image height.
+    pub height: usize,
+}
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    type Input = Image;
+    type Output = u64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let lines = parse::rectangle(input)?;
+        let mut locs = vec![];
+        for (line_num, line) in lines.iter().enumerate() {
+            for (x, b) in line.as_bytes().iter().enumerate() {
+                match b {
+                    b'.' => {}
+                    b'#' => {
+                        locs.push(Location(isize::try_from(x)?, isize::try_from(line_num)?));
+                    }
+                    _ => {
+                        return Err(
+                            ParseError::at_byte(line_num + 1, line, x, "invalid space").into()
+                        )
+                    }
+                }
+            }
+        }
+        Ok(Image {
+            locs,
+            width: lines[0].len(),
+            height: lines.len(),
+        })
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<u64> {
+        Ok(distances(&expand(input, 1)))
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<u64> {
+        Ok(distances(&expand(input, 999_999)))
+    }
+}
+
+// Take each original loc and move it along by add for every empty row/column
+// before it.
+fn expand(image: &Image, add: isize) -> Vec<Location> {
+    let locs = &image.locs;
+    let cols = locs.iter().map(|f| f.1).collect::<HashSet<_>>();
+    let rows = locs.iter().map(|f| f.0).collect::<HashSet<_>>();
+    let mut empty_cols = vec![];
+    let mut empty_rows = vec![];
+    for i in 0..image.width {
+        let x = isize::try_from(i).unwrap();
+        if !cols.contains(&x) {
+            empty_rows.push(x);
+        }
+    }
+    for i in 0..image.height {
+        let y = isize::try_from(i).unwrap();
+        if !rows.contains(&y) {
+            empty_cols.push(y);
+        }
+    }
+    debug!(?empty_cols, ?empty_rows);
+
+    let mut adjusted_locs = vec![];
+    for l in locs {
+        let mut new = l.clone();
+        for c in &empty_cols {
+            if l.0 > *c {
+                new.0 += add;
+            }
+        }
+        for r in &empty_rows {
+            if l.1 > *r {
+                new.1 += add;
+            }
+        }
+        adjusted_locs.push(new);
+    }
+
+    // Only draw the expanded image when it's small enough to look at (part2's
+    // is millions wide).
+    if add == 1 && enabled!(Level::TRACE) {
+        trace!("image:\n{}", render(image.width, image.height, locs));
+        trace!(
+            "expanded:\n{}",
+            render(
+                image.width + empty_cols.len(),
+                image.height + empty_rows.len(),
+                &adjusted_locs,
+            )
+        );
+    }
+    adjusted_locs
+}
+
+// Sum the distance between every pair of galaxies.
+fn distances(locs: &[Location]) -> u64 {
+    locs.iter()
+        .combinations(2)
+        .fold(0, |acc, f| acc + u64::from(f[0].distance(f[1])))
+}
+
+fn render(width: usize, height: usize, locs: &[Location]) -> String {
+    let c = locs.iter().collect::<HashSet<_>>();
+    let mut out = String::new();
+    for y in 0..height {
+        for x in 0..width {
+            let x = isize::try_from(x).unwrap();
+            let y = isize::try_from(y).unwrap();
+            if c.contains(&Location(x, y)) {
+                out.push('#');
+            } else {
+                out.push('.');
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
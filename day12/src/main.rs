@@ -1,37 +1,23 @@
-//! day14 advent 20XX
+//! day12 advent 2023
 use clap::Parser;
 use color_eyre::eyre::Result;
-use grid::{Grid, Location};
-use itertools::Itertools;
-use slab_tree::tree::TreeBuilder;
-use std::collections::HashMap;
-use std::fs::File;
-use std::io;
-use std::io::BufRead;
-use std::path::Path;
-use strum_macros::Display;
+use day12::Day12;
 
 #[derive(Parser)]
 #[command(author, version, about)]
 struct Args {
-    #[arg(long, default_value_t = String::from("input.txt"))]
-    filename: String,
-
-    #[arg(long, default_value_t = false)]
-    debug: bool,
+    #[command(flatten)]
+    common: aoc_common::Args,
 }
 
 fn main() -> Result<()> {
     color_eyre::install()?;
     let args: Args = Args::parse();
+    aoc_common::trace::init(&args.common.trace)?;
 
-    let filename = Path::new(env!("CARGO_MANIFEST_DIR")).join(args.filename);
-    let file = File::open(filename)?;
-    let lines: Vec<String> = io::BufReader::new(file)
-        .lines()
-        .map_while(Result::ok)
-        .collect();
-
-    for (line_num, line) in lines.iter().enumerate() {}
-    Ok(())
+    aoc_common::run(
+        &Day12,
+        &args.common.input(env!("CARGO_MANIFEST_DIR")),
+        args.common.format,
+    )
 }
@@ -0,0 +1,213 @@
+//! day13 advent 20XX
+use aoc_common::{ParseError, Solution};
+use color_eyre::eyre::{eyre, Result};
+use tracing::{debug, trace};
+
+/// Point of incidence.
+#[derive(Debug, Default)]
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+    /// Each pattern as rows of 1 (rock) and 0 (ash).
+    type Input = Vec<Vec<Vec<i32>>>;
+    type Output = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let mut patterns = vec![];
+        for block in aoc_common::numbered_blocks(input) {
+            let mut pattern = vec![];
+            let width = block[0].1.len();
+            for (line_num, line) in block {
+                if line.len() != width {
+                    return Err(ParseError::line(
+                        line_num,
+                        line,
+                        &format!("expected {width} columns like the first line"),
+                    )
+                    .into());
+                }
+                let mut row = vec![];
+                for (pos, b) in line.as_bytes().iter().enumerate() {
+                    row.push(match b {
+                        b'#' => 1,
+                        b'.' => 0,
+                        _ => {
+                            return Err(
+                                ParseError::at_byte(line_num, line, pos, "invalid space").into()
+                            )
+                        }
+                    });
+                }
+                pattern.push(row);
+            }
+            patterns.push(pattern);
+        }
+        Ok(patterns)
+    }
+
+    // Run through the patterns and find the one without any changes.
+    fn part1(&self, input: &Self::Input) -> Result<usize> {
+        input
+            .iter()
+            .enumerate()
+            .map(|(i, p)| find_reflect(i + 1, p, false))
+            .sum()
+    }
+
+    // Then do it again requiring a smudge fix.
+    fn part2(&self, input: &Self::Input) -> Result<usize> {
+        input
+            .iter()
+            .enumerate()
+            .map(|(i, p)| find_reflect(i + 1, p, true))
+            .sum()
+    }
+}
+
+// For a given grid (pattern number `n`) try and find a mirror horizontally
+// first, then flip it into a column rotation and try again. Returns 100 times
+// the rows above a horizontal mirror or the columns left of a vertical one.
+// The input says we're guarenteed to find a solution in this.
+fn find_reflect(n: usize, p: &[Vec<i32>], find_smudge: bool) -> Result<usize> {
+    debug!("pattern:\n{}", render(p));
+    if let Some(r) = find_mirror(p, find_smudge) {
+        debug!("Found mirror and {r} rows above.");
+        Ok(100 * r)
+    } else {
+        let c = column_vec(p);
+        debug!("rotated:\n{}", render(&c));
+        // Input claims we're guarenteed to find a reflection so if the
+        // above didn't this must or something is broken..
+        let r = find_mirror(&c, find_smudge)
+            .ok_or_else(|| eyre!("pattern {n} has no line of reflection"))?;
+        debug!("Found mirror and {r} rows to the left.");
+        Ok(r)
+    }
+}
+
+// For each row in a given grid walk from the 2nd row onwards comparing
+// (via rows_equal so we can account for smudge correction). Each time
+// we find a match loop expanding the rows out to match until we hit an edge.
+// If we matched all the way back to the edge this is a valid mirror. Otherwise
+// one didn't exist.
+fn find_mirror(p: &[Vec<i32>], find_smudge: bool) -> Option<usize> {
+    for pp in 1..p.len() {
+        #[allow(clippy::cast_possible_wrap)]
+        let mut right = pp as isize;
+        let mut gap = 1_isize;
+        let mut ret_find_smudge = find_smudge;
+        loop {
+            #[allow(clippy::cast_sign_loss)]
+            let ret = rows_equal(
+                &p[right as usize],
+                &p[(right - gap) as usize],
+                ret_find_smudge,
+            );
+            if ret.0 {
+                trace!(
+                    "Found at {right} and {} with smudge: {find_smudge}",
+                    right - gap
+                );
+                if ret.1 {
+                    // Once we've corrected one place and gotten a further match we quit correcting for
+                    // this iteration.
+                    ret_find_smudge = false;
+                }
+                if right + 1 < p.len().try_into().unwrap() && ((right + 1) - (gap + 2)) >= 0 {
+                    right += 1;
+                    gap += 2;
+                    trace!("Trying {right} and {}", right - gap);
+                    continue;
+                }
+
+                // If we must find a smudge and we've matched completely but
+                // never corrected (i.e. ret_find_smudge is still true) then
+                // this isn't a match for part2.
+                if find_smudge {
+                    if ret_find_smudge {
+                        break;
+                    }
+                    return Some(pp);
+                }
+                return Some(pp);
+            }
+            break;
+        }
+    }
+    None
+}
+
+// For 2 rows check if they're equal. If not and we require finding a smudge
+// correction start trying to change each entry for one row until this either
+// does match or we can't find a match for this pair of rows.
+//
+// Returns (found, corrected) where found indicates if the rows matched and
+// corrected indicates a smudge correction was used to perform this.
+//
+// NOTE: In real code this would be a struct as bool, bool is confusing to keep straight otherwise.
+fn rows_equal(p1: &Vec<i32>, p2: &Vec<i32>, find_smudge: bool) -> (bool, bool) {
+    if *p1 == *p2 {
+        return (true, false);
+    }
+    if !find_smudge {
+        return (false, false);
+    }
+
+    let mut smudge_p2 = p2.clone();
+    for i in 0..smudge_p2.len() {
+        let orig = smudge_p2[i];
+        if orig == 0 {
+            smudge_p2[i] = 1;
+        } else {
+            smudge_p2[i] = 0;
+        }
+        if smudge_p2 == *p1 {
+            trace!("Found smudge at position {i}");
+            return (true, true);
+        }
+        smudge_p2[i] = orig;
+    }
+    (false, false)
+}
+
+// Take a grid and rotate it clockwise 90 degrees and return a new grid.
+fn column_vec(pattern: &[Vec<i32>]) -> Vec<Vec<i32>> {
+    // Pre-reserve capacity in each so we can just iterate and place.
+    let mut ret = vec![vec![0; pattern.len()]; pattern[0].len()];
+
+    for y in 0..pattern.len() {
+        let retx = ret[0].len() - 1 - y;
+        #[allow(clippy::needless_range_loop)]
+        for x in 0..pattern[0].len() {
+            ret[x][retx] = pattern[y][x];
+        }
+    }
+    ret
+}
+
+// Draw the given grid for debugging.
+fn render(pattern: &[Vec<i32>]) -> String {
+    let mut out = String::new();
+    for y in pattern {
+        for x in y {
+            out.push(if *x == 0 { '.' } else { '#' });
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_reflection() -> Result<()> {
+        let day = Day13;
+        let input = day.parse("##\n##\n\n#.\n.#\n")?;
+        let err = day.part1(&input).unwrap_err();
+        assert_eq!(err.to_string(), "pattern 2 has no line of reflection");
+        Ok(())
+    }
+}
@@ -0,0 +1,506 @@
+//! day14 advent 20XX
+use aoc_common::animate::{self, Screen};
+use aoc_common::render::{self, Image, Rgb};
+use aoc_common::{cycle, parse, trace, ParseError, Solution};
+use color_eyre::eyre::{eyre, Result};
+use core::fmt;
+use grid::{Grid, Location};
+use tracing::{debug, trace};
+
+/// Parabolic reflector dish.
+#[derive(Debug, Default)]
+pub struct Day14;
+
+/// One spot on the platform.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Entry {
+    /// Nothing here.
+    #[default]
+    Empty,
+    /// A rock which rolls.
+    Round,
+    /// A rock which stays put.
+    Cube,
+}
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+    type Input = Grid<Entry>;
+    type Output = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let lines = parse::rectangle(input)?;
+        let mut grid = Grid::<Entry>::new(lines[0].len(), lines.len());
+        for (line_num, line) in lines.iter().enumerate() {
+            for (pos, b) in line.as_bytes().iter().enumerate() {
+                let x = isize::try_from(pos)?;
+                let y = isize::try_from(line_num)?;
+                grid.add(
+                    &Location(x, y),
+                    match b {
+                        b'.' => Entry::Empty,
+                        b'#' => Entry::Cube,
+                        b'O' => Entry::Round,
+                        _ => {
+                            return Err(ParseError::at_byte(
+                                line_num + 1,
+                                line,
+                                pos,
+                                "invalid rock",
+                            )
+                            .into())
+                        }
+                    },
+                );
+            }
+        }
+
+        debug!("platform:\n{}", trace::grid(&grid));
+        Ok(grid)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<usize> {
+        Ok(compute_load(&north_grid(input)))
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<usize> {
+        load_after(input, 1_000_000_000)
+    }
+}
+
+/// The load on the north beams after `cycles` spin cycles.
+///
+/// Even part 2's billion cycles would be far too slow to simulate. The
+/// platform eventually falls into a loop though so this finds that
+/// (comparing where the round rocks are, not just the load) and indexes
+/// into it instead. See [`spin`] for the simple version.
+///
+/// # Errors
+/// If no loop shows up within `10000` cycles or drawing a frame fails.
+pub fn load_after(grid: &Grid<Entry>, cycles: usize) -> Result<usize> {
+    show(grid)?;
+    // Drawing can fail part way so hang onto the first error until the
+    // search is done.
+    let mut drawn = Ok(());
+    let step = |g: &Grid<Entry>| {
+        let next = do_cycle(g);
+        if drawn.is_ok() {
+            drawn = show(&next);
+        }
+        next
+    };
+    let c = cycle::find_by_key(grid.clone(), step, round_rocks, 10_000)
+        .ok_or_else(|| eyre!("no repeat found after 10000 cycles"))?;
+    drawn?;
+    debug!(prefix = c.prefix_len, period = c.period, "found a loop");
+    Ok(compute_load(c.state(cycles)))
+}
+
+/// Slow reference for [`load_after`] which really does run every spin
+/// cycle.
+///
+/// Each tilt just keeps moving round rocks one space at a time until none
+/// can move any further.
+#[must_use]
+pub fn spin(grid: &Grid<Entry>, cycles: usize) -> usize {
+    let mut rows = vec![vec![Entry::Empty; grid.width()]; grid.height()];
+    for (l, e) in grid {
+        #[allow(clippy::cast_sign_loss)]
+        let (x, y) = (l.0 as usize, l.1 as usize);
+        rows[y][x] = e.clone();
+    }
+    for _ in 0..cycles {
+        // North, west, south and then east as (dx, dy).
+        for (dx, dy) in [(0, -1), (-1, 0), (0, 1), (1, 0)] {
+            while tilt_once(&mut rows, dx, dy) {}
+        }
+    }
+    rows.iter()
+        .enumerate()
+        .map(|(y, r)| (grid.height() - y) * r.iter().filter(|e| **e == Entry::Round).count())
+        .sum()
+}
+
+// Move every round rock which can go one space towards dx, dy. Returns
+// whether any did.
+fn tilt_once(rows: &mut [Vec<Entry>], dx: isize, dy: isize) -> bool {
+    let mut moved = false;
+    for y in 0..rows.len() {
+        for x in 0..rows[y].len() {
+            let (Some(nx), Some(ny)) = (x.checked_add_signed(dx), y.checked_add_signed(dy)) else {
+                continue;
+            };
+            if rows[y][x] == Entry::Round
+                && rows.get(ny).and_then(|r| r.get(nx)) == Some(&Entry::Empty)
+            {
+                rows[ny][nx] = Entry::Round;
+                rows[y][x] = Entry::Empty;
+                moved = true;
+            }
+        }
+    }
+    moved
+}
+
+// Where every round rock is which is all that changes between cycles.
+fn round_rocks(grid: &Grid<Entry>) -> Vec<Location> {
+    grid.iter()
+        .filter(|e| *e.1 == Entry::Round)
+        .map(|e| e.0)
+        .collect()
+}
+
+fn do_cycle(grid: &Grid<Entry>) -> Grid<Entry> {
+    let north_grid = north_grid(grid);
+    trace!(
+        "north: {}\n{}",
+        compute_load(&north_grid),
+        trace::grid(&north_grid)
+    );
+
+    let west_grid = west_grid(&north_grid);
+    trace!(
+        "west: {}\n{}",
+        compute_load(&west_grid),
+        trace::grid(&west_grid)
+    );
+
+    let south_grid = south_grid(&west_grid);
+    trace!(
+        "south: {}\n{}",
+        compute_load(&south_grid),
+        trace::grid(&south_grid)
+    );
+
+    let east_grid = east_grid(&south_grid);
+    trace!(
+        "east: {}\n{}",
+        compute_load(&east_grid),
+        trace::grid(&east_grid)
+    );
+
+    east_grid
+}
+
+// Draw a frame of the spin cycle to disk and/or the terminal.
+fn show(grid: &Grid<Entry>) -> Result<()> {
+    render::frame("day14-cycle", || Image::from_grid(grid, colour))?;
+    animate::frame("day14-cycle", || {
+        Screen::from_grid(grid, |e| match e {
+            // Black on black would hide the floor in the terminal.
+            Entry::Empty => Rgb::GREY,
+            _ => colour(e),
+        })
+    })
+}
+
+fn colour(e: &Entry) -> Rgb {
+    match e {
+        Entry::Empty => Rgb::BLACK,
+        Entry::Round => Rgb::WHITE,
+        Entry::Cube => Rgb(0xC0, 0x60, 0x20),
+    }
+}
+
+fn compute_load(grid: &Grid<Entry>) -> usize {
+    // Compute the load.
+    let mut sum = 0;
+    let h = grid.height();
+    for e in grid {
+        if *e.1 == Entry::Round {
+            #[allow(clippy::cast_sign_loss)]
+            let y = e.0 .1 as usize;
+            sum += h - y;
+        }
+    }
+    sum
+}
+
+fn north_grid(grid: &Grid<Entry>) -> Grid<Entry> {
+    // Move all the Round entries as far forward (north) as we can. Just record
+    // how many are in a line before they either stop at a cube or the bottom.
+    // Then copy that many over to the new grid and copy the cubes as well
+    // (technically we don't need the cubes for the problem but it makes it easier
+    //  to visualize).
+    let mut north_grid = Grid::<Entry>::new(grid.width(), grid.height());
+
+    let x_max: isize = grid.width().try_into().unwrap();
+    let y_max: isize = grid.height().try_into().unwrap();
+
+    let mut start = Location(0, 0);
+    let mut cur = start.clone();
+    let mut count = 0;
+    loop {
+        let mut col_done = false;
+        match grid.get(&cur) {
+            Entry::Empty => {
+                if cur.1 + 1 == y_max {
+                    col_done = true;
+                } else {
+                    cur.1 += 1;
+                }
+            }
+            Entry::Round => {
+                count += 1;
+                if cur.1 + 1 == y_max {
+                    col_done = true;
+                } else {
+                    cur.1 += 1;
+                }
+            }
+            Entry::Cube => {
+                for _ in 0..count {
+                    north_grid.add(&start, Entry::Round);
+                    start.1 += 1;
+                }
+                count = 0;
+                north_grid.add(&cur, Entry::Cube);
+                if cur.1 + 1 == y_max {
+                    // We're at the right bottom so finish.
+                    if cur.0 + 1 == x_max {
+                        break;
+                    }
+                    cur.0 += 1;
+                    cur.1 = 0;
+                } else {
+                    cur.1 += 1;
+                }
+                start = cur.clone();
+            }
+        }
+        if col_done {
+            for _ in 0..count {
+                north_grid.add(&start, Entry::Round);
+                start.1 += 1;
+            }
+            // We're at the right bottom so finish.
+            if cur.0 + 1 == x_max {
+                break;
+            }
+            cur.0 += 1;
+            cur.1 = 0;
+            start = cur.clone();
+            count = 0;
+        }
+    }
+    north_grid
+}
+
+fn south_grid(grid: &Grid<Entry>) -> Grid<Entry> {
+    // Move all the Round entries as far down (south) as we can. Just record
+    // how many are in a line before they either stop at a cube or the bottom.
+    // Then copy that many over to the new grid and copy the cubes as well
+    // (technically we don't need the cubes for the problem but it makes it easier
+    //  to visualize).
+    let mut south_grid = Grid::<Entry>::new(grid.width(), grid.height());
+
+    let start_y = (grid.height() - 1).try_into().unwrap();
+    let x_max: isize = grid.width().try_into().unwrap();
+    let mut start = Location(0, start_y);
+    let mut cur = start.clone();
+    let mut count = 0;
+    loop {
+        let mut col_done = false;
+        match grid.get(&cur) {
+            Entry::Empty => {
+                if cur.1 - 1 < 0 {
+                    col_done = true;
+                } else {
+                    cur.1 -= 1;
+                }
+            }
+            Entry::Round => {
+                count += 1;
+                if cur.1 - 1 < 0 {
+                    col_done = true;
+                } else {
+                    cur.1 -= 1;
+                }
+            }
+            Entry::Cube => {
+                for _ in 0..count {
+                    south_grid.add(&start, Entry::Round);
+                    start.1 -= 1;
+                }
+                count = 0;
+                south_grid.add(&cur, Entry::Cube);
+                if cur.1 - 1 < 0 {
+                    // We're at the right top so finish.
+                    if cur.0 + 1 == x_max {
+                        break;
+                    }
+                    cur.0 += 1;
+                    cur.1 = start_y;
+                } else {
+                    cur.1 -= 1;
+                }
+                start = cur.clone();
+            }
+        }
+        if col_done {
+            for _ in 0..count {
+                south_grid.add(&start, Entry::Round);
+                start.1 -= 1;
+            }
+            // We're at the right top so finish.
+            if cur.0 + 1 == x_max {
+                break;
+            }
+            cur.0 += 1;
+            cur.1 = start_y;
+            start = cur.clone();
+            count = 0;
+        }
+    }
+    south_grid
+}
+
+fn east_grid(grid: &Grid<Entry>) -> Grid<Entry> {
+    // Move all the Round entries as far right (east) as we can. Just record
+    // how many are in a line before they either stop at a cube or the bottom.
+    // Then copy that many over to the new grid and copy the cubes as well
+    // (technically we don't need the cubes for the problem but it makes it easier
+    //  to visualize).
+    let mut east_grid = Grid::<Entry>::new(grid.width(), grid.height());
+
+    let start_x = (grid.width() - 1).try_into().unwrap();
+    let y_max: isize = grid.height().try_into().unwrap();
+
+    let mut start = Location(start_x, 0);
+    let mut cur = start.clone();
+    let mut count = 0;
+    loop {
+        let mut row_done = false;
+        match grid.get(&cur) {
+            Entry::Empty => {
+                if cur.0 - 1 < 0 {
+                    row_done = true;
+                } else {
+                    cur.0 -= 1;
+                }
+            }
+            Entry::Round => {
+                count += 1;
+                if cur.0 - 1 < 0 {
+                    row_done = true;
+                } else {
+                    cur.0 -= 1;
+                }
+            }
+            Entry::Cube => {
+                for _ in 0..count {
+                    east_grid.add(&start, Entry::Round);
+                    start.0 -= 1;
+                }
+                count = 0;
+                east_grid.add(&cur, Entry::Cube);
+                if cur.0 - 1 < 0 {
+                    // We're at the left bottom so finish.
+                    if cur.1 + 1 == y_max {
+                        break;
+                    }
+                    cur.0 = start_x;
+                    cur.1 += 1;
+                } else {
+                    cur.0 -= 1;
+                }
+                start = cur.clone();
+            }
+        }
+        if row_done {
+            for _ in 0..count {
+                east_grid.add(&start, Entry::Round);
+                start.0 -= 1;
+            }
+            // We're at the bottom so finish.
+            if cur.1 + 1 == y_max {
+                break;
+            }
+            cur.0 += start_x;
+            cur.1 += 1;
+            start = cur.clone();
+            count = 0;
+        }
+    }
+    east_grid
+}
+
+fn west_grid(grid: &Grid<Entry>) -> Grid<Entry> {
+    // Move all the Round entries as far left (west) as we can. Just record
+    // how many are in a line before they either stop at a cube or the bottom.
+    // Then copy that many over to the new grid and copy the cubes as well
+    // (technically we don't need the cubes for the problem but it makes it easier
+    //  to visualize).
+    let mut west_grid = Grid::<Entry>::new(grid.width(), grid.height());
+
+    let x_max: isize = grid.width().try_into().unwrap();
+    let y_max: isize = grid.height().try_into().unwrap();
+
+    let mut start = Location(0, 0);
+    let mut cur = start.clone();
+    let mut count = 0;
+    loop {
+        let mut row_done = false;
+        match grid.get(&cur) {
+            Entry::Empty => {
+                if cur.0 + 1 == x_max {
+                    row_done = true;
+                } else {
+                    cur.0 += 1;
+                }
+            }
+            Entry::Round => {
+                count += 1;
+                if cur.0 + 1 == x_max {
+                    row_done = true;
+                } else {
+                    cur.0 += 1;
+                }
+            }
+            Entry::Cube => {
+                for _ in 0..count {
+                    west_grid.add(&start, Entry::Round);
+                    start.0 += 1;
+                }
+                count = 0;
+                west_grid.add(&cur, Entry::Cube);
+                if cur.0 + 1 == x_max {
+                    // We're at the left bottom so finish.
+                    if cur.1 + 1 == y_max {
+                        break;
+                    }
+                    cur.0 = 0;
+                    cur.1 += 1;
+                } else {
+                    cur.0 += 1;
+                }
+                start = cur.clone();
+            }
+        }
+        if row_done {
+            for _ in 0..count {
+                west_grid.add(&start, Entry::Round);
+                start.0 += 1;
+            }
+            // We're at the bottom so finish.
+            if cur.1 + 1 == y_max {
+                break;
+            }
+            cur.0 = 0;
+            cur.1 += 1;
+            start = cur.clone();
+            count = 0;
+        }
+    }
+    west_grid
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Entry::Empty => write!(f, "."),
+            Entry::Cube => write!(f, "#"),
+            Entry::Round => write!(f, "O"),
+        }
+    }
+}
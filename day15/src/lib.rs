@@ -0,0 +1,131 @@
+//! day15 advent 20XX
+use aoc_common::{ParseError, Solution};
+use color_eyre::eyre::{eyre, Result};
+use strum_macros::Display;
+use tracing::{enabled, trace, Level};
+
+/// Lens library.
+#[derive(Debug, Default)]
+pub struct Day15;
+
+#[derive(Clone, Debug, Display, PartialEq)]
+enum Op {
+    Dash,
+    Equals(usize),
+}
+
+#[derive(Clone, Debug)]
+struct Label<'a>(&'a str, usize);
+
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+    /// Each comma separated step.
+    type Input = Vec<String>;
+    type Output = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        // For this we can assume there's one line.
+        let line = input.lines().next().unwrap_or_default();
+        let mut steps = vec![];
+        for step in line.split(',') {
+            // Part1 just hashes everything but make sure it's something part2
+            // can use.
+            if hash2(step).is_none() {
+                return Err(ParseError::at(1, line, step, "expected label- or label=N").into());
+            }
+            steps.push(step.to_string());
+        }
+        Ok(steps)
+    }
+
+    // For part1 compute a hash of the whole string. Then sum them up.
+    fn part1(&self, input: &Self::Input) -> Result<usize> {
+        Ok(input.iter().map(|p| hash(p)).sum())
+    }
+
+    // Part2 is a bit more complicated.
+    // We only want to hash until we find a '-' or '=' sign.
+    // We're going to track 256 buckets where each one contains N length
+    // array (in order of insertion/update) which contains the string we hashed
+    // along with whatever number is after the equals.
+    // The bucket is the hash value of this new hash.
+    //
+    // For dash if the entry exists simply remove it from the list and shift over.
+    // For equal we either add it (if it doesn't exist) or replace the value for
+    // the existing entry in place.
+    //
+    // NOTE: This assumes small enough vectors that linear search here doesn't
+    //       matter and we'll just user iter().find() or retain() to do updates.
+    fn part2(&self, input: &Self::Input) -> Result<usize> {
+        let mut boxes: Vec<Vec<Label>> = vec![vec![]; 256];
+        for p in input {
+            let op = hash2(p).ok_or_else(|| eyre!("invalid step {p}"))?;
+            trace!("{p} -> {op:?}");
+            match op.2 {
+                Op::Dash => {
+                    // Keep anything which doesn't match the label we hashed.
+                    // NOTE: This is potentially expensive if we do a lot of these
+                    //       as it shifts the vector everytime.
+                    boxes[op.0].retain(|b| b.0 != op.1);
+                }
+                Op::Equals(v) => {
+                    // If we already have an entry update it via a mutable find.
+                    // Otherwise just add it onto the end.
+                    if let Some(f) = boxes[op.0].iter_mut().find(|p| p.0 == op.1) {
+                        f.1 = v;
+                    } else {
+                        boxes[op.0].push(Label(op.1, v));
+                    }
+                }
+            }
+            if enabled!(Level::TRACE) {
+                log_boxes(&boxes);
+            }
+        }
+
+        let mut part2_sum = 0;
+        for (pos, b) in boxes.iter().enumerate() {
+            for (lenspos, l) in b.iter().enumerate() {
+                part2_sum += (pos + 1) * (lenspos + 1) * l.1;
+            }
+        }
+        Ok(part2_sum)
+    }
+}
+
+fn log_boxes(boxes: &[Vec<Label>]) {
+    for (pos, b) in boxes.iter().enumerate() {
+        if !b.is_empty() {
+            trace!("Box {pos}: {b:?}");
+        }
+    }
+}
+
+fn hash(p: &str) -> usize {
+    let mut val = 0;
+    for b in p.bytes() {
+        val = hash_logic(val, b);
+    }
+    val
+}
+
+fn hash_logic(val: usize, b: u8) -> usize {
+    ((val + usize::from(b)) * 17) % 256
+}
+
+fn hash2(p: &str) -> Option<(usize, &str, Op)> {
+    let mut val = 0;
+    for (pos, b) in p.bytes().enumerate() {
+        match b {
+            b'-' if pos == p.len() - 1 => return Some((val, &p[0..pos], Op::Dash)),
+            b'=' => {
+                let num = p[pos + 1..].parse::<usize>().ok()?;
+                return Some((val, &p[0..pos], Op::Equals(num)));
+            }
+            _ => {
+                val = hash_logic(val, b);
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,284 @@
+//! day16 advent 20XX
+use aoc_common::animate::{self, Screen};
+use aoc_common::direction::Direction::{self, East, North, South, West};
+use aoc_common::render::{self, Image, Rgb};
+use aoc_common::{parse, trace, ParseError, Solution, Step};
+use color_eyre::eyre::Result;
+use core::fmt;
+use grid::{Grid, Location};
+use std::collections::HashSet;
+use tracing::{debug, debug_span, trace};
+
+/// The floor will be lava.
+#[derive(Debug, Default)]
+pub struct Day16;
+
+/// One tile of the contraption.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Entry {
+    /// .
+    #[default]
+    Empty,
+    /// /
+    MirrorForward,
+    /// \\
+    MirrorBackward,
+    /// |
+    SplitterUp,
+    /// -
+    SplitterSide,
+}
+
+// Colour for energized tiles in the terminal.
+const BEAM: Rgb = Rgb(0xFF, 0xC0, 0x20);
+
+#[derive(Clone, Debug, Default, PartialEq)]
+enum Energized {
+    #[default]
+    Empty,
+    Entered(HashSet<Direction>),
+}
+
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+    type Input = Grid<Entry>;
+    type Output = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let lines = parse::rectangle(input)?;
+        let mut grid = Grid::<Entry>::new(lines[0].len(), lines.len());
+        for (line_num, line) in lines.iter().enumerate() {
+            for (x, b) in line.bytes().enumerate() {
+                let e = match b {
+                    b'.' => Entry::Empty,
+                    b'|' => Entry::SplitterUp,
+                    b'-' => Entry::SplitterSide,
+                    b'/' => Entry::MirrorForward,
+                    b'\\' => Entry::MirrorBackward,
+                    _ => {
+                        return Err(
+                            ParseError::at_byte(line_num + 1, line, x, "invalid tile").into()
+                        )
+                    }
+                };
+                grid.add(&Location(x.try_into()?, line_num.try_into()?), e);
+            }
+        }
+        debug!("contraption:\n{}", trace::grid(&grid));
+        Ok(grid)
+    }
+
+    // For part1 we always start in the upper left facing east and then walk and count.
+    fn part1(&self, input: &Self::Input) -> Result<usize> {
+        walk_grid(input, &Location(0, 0), East)
+    }
+
+    // For part2 we need to start on every outside location and each possible
+    // direction that could use. i.e. more are one direction but each corner
+    // has 2. Then find the max tiles energized after trying all of these.
+    fn part2(&self, input: &Self::Input) -> Result<usize> {
+        let grid = input;
+        #[allow(clippy::cast_possible_wrap)]
+        let max_x = grid.width() as isize - 1;
+        #[allow(clippy::cast_possible_wrap)]
+        let max_y = grid.height() as isize - 1;
+
+        let mut choices = vec![
+            (Location(0, 0), East),          // Upper left
+            (Location(0, 0), South),         // Upper left
+            (Location(0, max_y), East),      // Bottom left
+            (Location(0, max_y), North),     // Bottom left
+            (Location(max_x, 0), West),      // Upper right
+            (Location(max_x, 0), South),     // Upper right
+            (Location(max_x, max_y), West),  // Bottom right
+            (Location(max_x, max_y), North), // Bottom right
+        ];
+        for x in 1..max_x {
+            choices.push((Location(x, 0), South)); // Top row not corners
+            choices.push((Location(x, max_y), North)); // Bottom row not corners
+        }
+        for y in 1..max_y {
+            choices.push((Location(0, y), East)); // Left edge not corners.
+            choices.push((Location(max_x, y), East)); // Right edge not corners.
+        }
+
+        let mut max = 0;
+        for (start, dir) in &choices {
+            max = max.max(walk_grid(grid, start, *dir)?);
+        }
+        Ok(max)
+    }
+}
+
+// Depending on initial facing more than one path may have to be initially
+// evaluated or the resulting initial step turns before we start. Account for
+// all those conditions here.
+fn setup_initial_work(
+    work: &mut Vec<(Location, Direction)>,
+    grid: &Grid<Entry>,
+    start: &Location,
+    init_dir: Direction,
+) {
+    // Even though we start facing one way the initial mirror may immediately repoint us so do that now
+    // since the loop below will not do that.
+    let dir = match init_dir {
+        North => match grid.get(start) {
+            Entry::Empty | Entry::SplitterUp => North,
+            Entry::SplitterSide => {
+                work.push((start.clone(), East));
+                West
+            }
+            Entry::MirrorBackward => West,
+            Entry::MirrorForward => East,
+        },
+
+        South => match grid.get(start) {
+            Entry::Empty | Entry::SplitterUp => South,
+            Entry::SplitterSide => {
+                work.push((start.clone(), East));
+                West
+            }
+            Entry::MirrorBackward => East,
+            Entry::MirrorForward => West,
+        },
+        East => match grid.get(start) {
+            Entry::Empty | Entry::SplitterSide => East,
+            Entry::MirrorBackward => South,
+            Entry::SplitterUp => {
+                work.push((start.clone(), North));
+                South
+            }
+            Entry::MirrorForward => North,
+        },
+        West => match grid.get(start) {
+            Entry::Empty | Entry::SplitterSide => West,
+            Entry::MirrorBackward => North,
+            Entry::SplitterUp => {
+                work.push((start.clone(), South));
+                North
+            }
+            Entry::MirrorForward => South,
+        },
+    };
+
+    work.push((start.clone(), dir));
+}
+
+fn walk_grid(grid: &Grid<Entry>, start: &Location, init_dir: Direction) -> Result<usize> {
+    let _walk = debug_span!("walk", %start, ?init_dir).entered();
+    let mut energized_grid = Grid::<Energized>::new(grid.width(), grid.height());
+    let mut work = vec![];
+    setup_initial_work(&mut work, grid, start, init_dir);
+
+    // DFS the space and make sure to ignore paths we have looped back around onto.
+    // i.e. one you enter a given location in a direction you never need to eval
+    // that again. That's the short circuit that makes this workable in O(4N) time.
+    // (you might have to visit every of the N squares 4 times due to each direction).
+    while let Some(c) = work.pop() {
+        trace!("Processing: {c:?}");
+        match energized_grid.get_mut(&c.0) {
+            Energized::Empty => {
+                energized_grid.add(&c.0, Energized::Entered(HashSet::from([c.1])));
+            }
+            Energized::Entered(hs) => {
+                if hs.contains(&c.1) {
+                    // If we've already been here in this direction no need to replay.
+                    continue;
+                }
+                hs.insert(c.1);
+            }
+        }
+        animate::frame("day16", || {
+            let mut s = Screen::from_grid(grid, |_| Rgb::GREY);
+            for (l, e) in &energized_grid {
+                if e != &Energized::Empty {
+                    s.put(
+                        &l,
+                        grid.get(&l).to_string().chars().next().unwrap_or('#'),
+                        BEAM,
+                    );
+                }
+            }
+            s.put(&c.0, '*', Rgb::WHITE);
+            s
+        })?;
+        // Going off any edge ends this path.
+        let Some(next) = c.0.step_within(c.1, grid) else {
+            continue;
+        };
+        trace!("next -> {next:?}");
+        match grid.get(&next) {
+            // Empty we just keep moving along.
+            Entry::Empty => work.push((next, c.1)),
+            Entry::MirrorForward => match c.1 {
+                North => work.push((next, East)),
+                South => work.push((next, West)),
+                East => work.push((next, North)),
+                West => work.push((next, South)),
+            },
+            Entry::MirrorBackward => match c.1 {
+                North => work.push((next, West)),
+                South => work.push((next, East)),
+                East => work.push((next, South)),
+                West => work.push((next, North)),
+            },
+            Entry::SplitterUp => {
+                if c.1 == East || c.1 == West {
+                    work.push((next.clone(), North));
+                    work.push((next, South));
+                } else {
+                    work.push((next, c.1));
+                }
+            }
+            Entry::SplitterSide => {
+                if c.1 == North || c.1 == South {
+                    work.push((next.clone(), East));
+                    work.push((next, West));
+                } else {
+                    work.push((next, c.1));
+                }
+            }
+        }
+    }
+    debug!("energized:\n{}", trace::grid(&energized_grid));
+    render::frame("day16", || {
+        // Energized tiles glow over the top of the contraption.
+        let mut im = Image::from_grid(grid, |e| match e {
+            Entry::Empty => Rgb::BLACK,
+            _ => Rgb::GREY,
+        });
+        for (l, e) in &energized_grid {
+            if let Energized::Entered(dirs) = e {
+                let heat = u8::try_from(dirs.len() * 0x30).unwrap_or(u8::MAX);
+                im.put(&l, Rgb(0xFF, 0x80 + heat / 2, heat));
+            }
+        }
+        im
+    })?;
+
+    Ok(energized_grid
+        .iter()
+        .filter(|p| p.1 != &Energized::Empty)
+        .count())
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Entry::Empty => write!(f, "."),
+            Entry::MirrorForward => write!(f, "/"),
+            Entry::MirrorBackward => write!(f, "\\"),
+            Entry::SplitterUp => write!(f, "|"),
+            Entry::SplitterSide => write!(f, "-"),
+        }
+    }
+}
+
+impl fmt::Display for Energized {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Energized::Empty => write!(f, "."),
+            Energized::Entered(_) => write!(f, "#"),
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! day17 advent 20XX
+use aoc_common::direction::Direction::{self, East, South};
+use aoc_common::render::{self, Image, Rgb};
+use aoc_common::search::{self, Problem};
+use aoc_common::{parse, trace, ParseError, Solution, Step};
+use color_eyre::eyre::{eyre, Result};
+use grid::{Grid, Location};
+use tracing::{debug, debug_span, trace};
+
+/// Clumsy crucible.
+#[derive(Debug, Default)]
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u32 = 17;
+    /// Heat loss for each block.
+    type Input = Grid<u32>;
+    type Output = u32;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let lines = parse::rectangle(input)?;
+        let mut grid = Grid::<u32>::new(lines[0].len(), lines.len());
+        for (y, line) in lines.iter().enumerate() {
+            for (x, c) in line.char_indices() {
+                let Some(heat) = c.to_digit(10) else {
+                    return Err(
+                        ParseError::at_byte(y + 1, line, x, "heat loss must be a digit").into(),
+                    );
+                };
+                grid.add(&Location(x.try_into()?, y.try_into()?), heat);
+            }
+        }
+
+        debug!("heat loss:\n{}", trace::grid(&grid));
+        Ok(grid)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<u32> {
+        let (cost, path) = cheapest(input, 0, 3)?;
+        draw(input, &path)?;
+        Ok(cost)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<u32> {
+        let (cost, path) = cheapest(input, 4, 10)?;
+        draw(input, &path)?;
+        Ok(cost)
+    }
+}
+
+/// Find the lowest heat loss path from the upper left to the lower right
+/// corner. The crucible must move at least `min` steps in a direction before
+/// turning (or stopping at the end) and can't move more than `max` steps in a
+/// row.
+///
+/// # Errors
+/// If there's no way to reach the end (or the heat loss doesn't fit a
+/// `u32`).
+pub fn run_grid(grid: &Grid<u32>, min: u32, max: u32) -> Result<u32> {
+    Ok(cheapest(grid, min, max)?.0)
+}
+
+// run_grid but also return every location along the way.
+fn cheapest(grid: &Grid<u32>, min: u32, max: u32) -> Result<(u32, Vec<Location>)> {
+    let _run = debug_span!("run_grid", min, max).entered();
+    #[allow(clippy::cast_possible_wrap)]
+    let end = Location(grid.width() as isize - 1, grid.height() as isize - 1);
+    let crucible = Crucible {
+        grid,
+        min,
+        max,
+        end,
+    };
+    let begin = Location(0, 0);
+    // Heat loss is a single digit so a bucket queue beats a heap.
+    let path = search::bucket(&crucible, [(begin.clone(), East, 0), (begin, South, 0)])
+        .ok_or_else(|| eyre!("no way to reach the end moving {min} to {max} steps at a time"))?;
+    debug!(
+        cost = path.cost,
+        moves = path.states.len() - 1,
+        "reached the end"
+    );
+    trace!("path {:?}", path.states);
+    let cost = u32::try_from(path.cost)?;
+    Ok((cost, path.states.into_iter().map(|s| s.0).collect()))
+}
+
+// Heat loss as shades of grey with the path over the top.
+fn draw(grid: &Grid<u32>, path: &[Location]) -> Result<()> {
+    render::frame("day17", || {
+        let mut im = Image::from_grid(grid, |h| Rgb::grey(*h, 9));
+        for l in path {
+            im.put(l, Rgb::RED);
+        }
+        im
+    })
+}
+
+// Moving a crucible around the grid. States are where it is, which way it's
+// going and how many steps it's taken that way.
+struct Crucible<'a> {
+    grid: &'a Grid<u32>,
+    min: u32,
+    max: u32,
+    end: Location,
+}
+
+impl Problem for Crucible<'_> {
+    type State = (Location, Direction, u32);
+
+    fn successors(&self, (loc, dir, steps): &Self::State) -> Vec<(Self::State, u64)> {
+        let mut next = vec![];
+        for newdir in Direction::ALL {
+            // Can't backup.
+            if newdir == dir.opposite() {
+                continue;
+            }
+            let Some(newloc) = loc.step_within(newdir, self.grid) else {
+                continue;
+            };
+
+            let count = if *dir == newdir {
+                // Can't go the same direction more than max times in a row.
+                if *steps >= self.max {
+                    continue;
+                }
+                steps + 1
+            } else {
+                // Must go min steps before changing directions.
+                if *steps < self.min {
+                    continue;
+                }
+                1
+            };
+
+            let cost = u64::from(*self.grid.get(&newloc));
+            next.push(((newloc, newdir, count), cost));
+        }
+        next
+    }
+
+    fn is_goal(&self, (loc, _, steps): &Self::State) -> bool {
+        *loc == self.end && *steps >= self.min
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The ultra crucible can't go 4 blocks in any direction here.
+    #[test]
+    fn unreachable() -> Result<()> {
+        let day = Day17;
+        let input = day.parse("1\n2\n3\n")?;
+        assert_eq!(day.part1(&input)?, 5);
+        assert!(day.part2(&input).is_err());
+        Ok(())
+    }
+}
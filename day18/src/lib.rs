@@ -0,0 +1,399 @@
+//! day18 advent 20XX
+use aoc_common::geometry::Polygon;
+use aoc_common::render::{self, Image, Rgb};
+use aoc_common::{parse, Direction, ParseError, Solution, Step};
+#[cfg(feature = "gui")]
+use color_eyre::eyre::eyre;
+use color_eyre::eyre::Result;
+#[cfg(feature = "gui")]
+use egui::{TextureHandle, TextureOptions};
+use grid::Location;
+use std::collections::{HashMap, HashSet};
+use tracing::{debug, trace};
+
+/// Lavaduct lagoon.
+#[derive(Debug, Default)]
+pub struct Day18 {
+    /// Visualization options.
+    pub options: Options,
+}
+
+/// Options for the map display.
+#[derive(Clone, Debug, clap::Args)]
+#[group(skip)]
+pub struct Options {
+    /// Show the map before and after the flood fill. Opens a window when
+    /// built with the gui feature and prints to stderr otherwise.
+    #[arg(long, default_value_t = false)]
+    pub show_map: bool,
+
+    /// Draw the map after every flood fill step. Written as frames with
+    /// --frames and printed to stderr otherwise.
+    #[arg(long, default_value_t = false)]
+    pub debug_full: bool,
+
+    /// How much to scale up the map window (gui feature only).
+    #[arg(long, default_value_t = 3.0)]
+    pub magnify: f32,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            show_map: false,
+            debug_full: false,
+            magnify: 3.0,
+        }
+    }
+}
+
+/// The dig plan both ways it can be read.
+#[derive(Clone, Debug, Default)]
+pub struct Plan {
+    /// Every edge location dug for part1 and the color for it.
+    pub locs: HashMap<Location, String>,
+    /// Polygon vertices from the plain instructions for part1. This starts
+    /// and ends at the origin.
+    pub dig: Vec<Location>,
+    /// Polygon vertices from the hex instructions for part2. This starts and
+    /// ends at the origin.
+    pub vertices: Vec<Location>,
+}
+
+#[cfg(feature = "gui")]
+struct MyApp {
+    texture: TextureHandle,
+    magnify: f32,
+}
+
+// A color we know isn't an edge color by checking the input we can use as the
+// flood fill instead.
+const INTERIOR: &str = "#9F0000";
+
+impl Solution for Day18 {
+    const DAY: u32 = 18;
+    type Input = Plan;
+    type Output = i128;
+
+    #[allow(clippy::too_many_lines)]
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let mut locs = HashMap::new();
+        let mut cur = Location(0, 0);
+        let mut part_loc = Location(0, 0);
+        locs.insert(cur.clone(), "#000000".to_string());
+
+        let mut dig = vec![cur.clone()];
+        let mut vertices = vec![part_loc.clone()];
+
+        let mut last_line = 0;
+        for (line_num, line) in input.lines().enumerate() {
+            let line_num = line_num + 1;
+            last_line = line_num;
+            let parts = line.split_whitespace().collect::<Vec<_>>();
+            if parts.len() != 3 {
+                return Err(
+                    ParseError::line(line_num, line, "expected DIR STEPS (#RRGGBB)").into(),
+                );
+            }
+
+            let steps = parse::number::<u32>(line_num, line, parts[1])?;
+            let code = parts[2];
+            if code.len() != 9
+                || !code.starts_with("(#")
+                || !code.ends_with(')')
+                || !code[2..8].bytes().all(|b| b.is_ascii_hexdigit())
+            {
+                return Err(ParseError::at(line_num, line, code, "expected (#RRGGBB)").into());
+            }
+            let color = &code[1..8];
+            let mut letters = parts[0].chars();
+            let dir = match (letters.next(), letters.next()) {
+                (Some(c), None) => Direction::from_letter(c),
+                _ => None,
+            };
+            let Some(dir) = dir else {
+                return Err(ParseError::at(
+                    line_num,
+                    line,
+                    parts[0],
+                    "direction must be U, D, L or R",
+                )
+                .into());
+            };
+            for _ in 0..steps {
+                cur = cur.step(dir);
+                locs.insert(cur.clone(), color.to_string());
+            }
+            dig.push(cur.clone());
+
+            let dist = isize::from_str_radix(&code[2..7], 16)?;
+            let Some(hex_dir) = code[7..8]
+                .chars()
+                .next()
+                .and_then(Direction::from_hex_digit)
+            else {
+                return Err(ParseError::at(
+                    line_num,
+                    line,
+                    &code[7..8],
+                    "hex direction must be 0-3",
+                )
+                .into());
+            };
+            trace!("{hex_dir} {dist}");
+            part_loc = part_loc.step_by(hex_dir, dist);
+            vertices.push(part_loc.clone());
+        }
+
+        // Make sure it came back to the start.
+        if part_loc != Location(0, 0) {
+            return Err(ParseError::new(
+                last_line,
+                1,
+                &part_loc.to_string(),
+                "hex plan doesn't return to the start",
+            )
+            .into());
+        }
+        if cur != Location(0, 0) {
+            return Err(ParseError::new(
+                last_line,
+                1,
+                &cur.to_string(),
+                "plan doesn't return to the start",
+            )
+            .into());
+        }
+        Ok(Plan {
+            locs,
+            dig,
+            vertices,
+        })
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<i128> {
+        let mut locs = input
+            .locs
+            .iter()
+            .map(|(k, v)| (k.clone(), v.as_str()))
+            .collect::<HashMap<_, _>>();
+        let polygon = Polygon::new(input.dig.clone())?;
+
+        // Take all the neighbors of 0,0 which are inside the trench and flood
+        // fill from there.
+        let mut testlocs = Location(0, 0)
+            .neighbors_all()
+            .into_iter()
+            .filter(|n| polygon.contains(n))
+            .collect::<Vec<_>>();
+        if self.options.show_map {
+            show_map(&locs, self.options.magnify)?;
+        }
+        render::frame("day18", || image(&locs))?;
+        while let Some(t) = testlocs.pop() {
+            for n in t.neighbors() {
+                if !locs.contains_key(&n) {
+                    testlocs.push(n);
+                }
+            }
+            locs.insert(t, INTERIOR);
+            if self.options.debug_full {
+                if render::enabled() {
+                    render::frame("day18", || image(&locs))?;
+                } else {
+                    eprintln!("{}", text_map(&locs));
+                }
+            }
+        }
+        if self.options.show_map {
+            show_map(&locs, self.options.magnify)?;
+        }
+        render::frame("day18", || image(&locs))?;
+        Ok(locs.len().try_into()?)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<i128> {
+        debug!("vertices:\n{:?}", input.vertices);
+        lagoon(&input.vertices)
+    }
+}
+
+/// How many cubic metres the trench with the given corners holds (the
+/// trench itself included).
+///
+/// Far too big to flood fill for part 2 so this counts lattice points
+/// instead. The trench is the boundary and Pick's theorem gives the inside.
+/// See [`flood_fill`] for the simple version.
+///
+/// # Errors
+/// If the vertices don't make a polygon.
+pub fn lagoon(vertices: &[Location]) -> Result<i128> {
+    let polygon = Polygon::new(vertices.to_vec())?;
+    let boundary = polygon.boundary_points();
+    let inside = polygon.interior_points();
+    debug!(area = polygon.area(), boundary, inside);
+    Ok(boundary + inside)
+}
+
+/// Slow reference for [`lagoon`] which digs out every cube.
+///
+/// The trench is dug on a grid one bigger than it all round and everything
+/// outside flood filled from a corner. Whatever that didn't reach is the
+/// lagoon. Only practical for small plans.
+#[must_use]
+pub fn flood_fill(vertices: &[Location]) -> usize {
+    let mut dug = HashSet::new();
+    for (i, from) in vertices.iter().enumerate() {
+        let to = &vertices[(i + 1) % vertices.len()];
+        let mut cur = from.clone();
+        dug.insert(cur.clone());
+        while cur != *to {
+            cur = Location(
+                cur.0 + (to.0 - cur.0).signum(),
+                cur.1 + (to.1 - cur.1).signum(),
+            );
+            dug.insert(cur.clone());
+        }
+    }
+
+    let xs = vertices.iter().map(|v| v.0);
+    let ys = vertices.iter().map(|v| v.1);
+    let (min_x, max_x) = (xs.clone().min().unwrap_or(0) - 1, xs.max().unwrap_or(0) + 1);
+    let (min_y, max_y) = (ys.clone().min().unwrap_or(0) - 1, ys.max().unwrap_or(0) + 1);
+    let mut outside = HashSet::new();
+    let mut work = vec![Location(min_x, min_y)];
+    while let Some(l) = work.pop() {
+        if l.0 < min_x || l.0 > max_x || l.1 < min_y || l.1 > max_y || dug.contains(&l) {
+            continue;
+        }
+        if outside.insert(l.clone()) {
+            work.extend(l.neighbors());
+        }
+    }
+    (min_x..=max_x).count() * (min_y..=max_y).count() - outside.len()
+}
+
+// Draw the map with each location in its own color on a grey background.
+fn image(locs: &HashMap<Location, &str>) -> Image {
+    Image::from_map(
+        locs.iter()
+            .map(|(l, c)| (l, Rgb::from_hex(c).unwrap_or(Rgb::WHITE))),
+        Rgb::GREY,
+    )
+}
+
+// Draw the map as text: # for the trench, I for the filled in interior and
+// . for everything else.
+fn text_map(locs: &HashMap<Location, &str>) -> String {
+    let min_x = locs.keys().map(|l| l.0).min().unwrap_or_default();
+    let max_x = locs.keys().map(|l| l.0).max().unwrap_or_default();
+    let min_y = locs.keys().map(|l| l.1).min().unwrap_or_default();
+    let max_y = locs.keys().map(|l| l.1).max().unwrap_or_default();
+
+    let mut s = String::new();
+    for y in min_y..=max_y {
+        for x in min_x..=max_x {
+            s.push(match locs.get(&Location(x, y)) {
+                Some(&INTERIOR) => 'I',
+                Some(_) => '#',
+                None => '.',
+            });
+        }
+        s.push('\n');
+    }
+    s
+}
+
+// Without a GUI the map just goes to stderr (stdout is for the answers).
+#[cfg(not(feature = "gui"))]
+#[allow(clippy::unnecessary_wraps)]
+fn show_map(locs: &HashMap<Location, &str>, _magnify: f32) -> Result<()> {
+    eprintln!("{}", text_map(locs));
+    Ok(())
+}
+
+#[cfg(feature = "gui")]
+fn show_map(locs: &HashMap<Location, &str>, magnify: f32) -> Result<()> {
+    display_map(&image(locs), magnify)
+}
+
+// Use egui to toss up a window so we can see the map.
+#[cfg(feature = "gui")]
+fn display_map(im: &Image, mult: f32) -> Result<()> {
+    let (width, height) = (im.width(), im.height());
+    debug!("Size: {width}x{height}");
+    let data = im.rgb_bytes();
+
+    let res = eframe::run_native(
+        "Debug path",
+        eframe::NativeOptions::default(),
+        Box::new(move |cc| Ok(Box::new(MyApp::new(cc, data, width, height, mult)))),
+    );
+
+    if let Err(e) = res {
+        return Err(eyre!("EGUI error: {e:?}"));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "gui")]
+impl MyApp {
+    #[allow(clippy::needless_pass_by_value)]
+    fn new(
+        cc: &eframe::CreationContext<'_>,
+        map: Vec<u8>,
+        width: usize,
+        height: usize,
+        magnify: f32,
+    ) -> Self {
+        let im = egui::ColorImage::from_rgb([width, height], &map);
+        let text = cc.egui_ctx.load_texture(
+            "debug map",
+            egui::ImageData::Color(im.into()),
+            TextureOptions::default(),
+        );
+
+        MyApp {
+            texture: text,
+            magnify,
+        }
+    }
+}
+
+#[cfg(feature = "gui")]
+impl eframe::App for MyApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::CentralPanel::default()
+            .frame(egui::Frame::new().fill(egui::Color32::GRAY))
+            .show(ctx, |ui| {
+                ui.add_space(10.0);
+
+                // Make a direct image allows us to scale it up vs using ui.image()
+                let mut s = self.texture.size_vec2();
+                s[0] *= self.magnify;
+                s[1] *= self.magnify;
+                ui.add(egui::Image::from_texture(&self.texture).fit_to_exact_size(s));
+            });
+        ctx.send_viewport_cmd(egui::ViewportCommand::MinInnerSize(ctx.used_size()));
+        ctx.send_viewport_cmd(egui::ViewportCommand::MaxInnerSize(ctx.used_size()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_as_text() -> Result<()> {
+        let day = Day18::default();
+        let input = day.parse("R 2 (#000000)\nD 2 (#000000)\nL 2 (#000000)\nU 2 (#000000)\n")?;
+        let mut locs = input
+            .locs
+            .iter()
+            .map(|(k, v)| (k.clone(), v.as_str()))
+            .collect::<HashMap<_, _>>();
+        locs.insert(Location(1, 1), INTERIOR);
+        assert_eq!(text_map(&locs), "###\n#I#\n###\n");
+        Ok(())
+    }
+}
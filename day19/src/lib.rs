@@ -0,0 +1,565 @@
+//! day19 advent 20XX
+use aoc_common::interval::{Cuboid, Interval};
+use aoc_common::{parse, ParseError, Solution};
+use color_eyre::eyre::{eyre, Result};
+use slab_tree::tree::TreeBuilder;
+use std::collections::{HashMap, HashSet};
+use strum_macros::Display;
+use tracing::{debug, enabled, trace, Level};
+
+/// Aplenty.
+#[derive(Debug, Default)]
+pub struct Day19;
+
+/// A machine part with its four ratings.
+#[derive(Clone, Debug, Default)]
+pub struct Part {
+    /// Extremely cool looking.
+    pub x: usize,
+    /// Musical.
+    pub m: usize,
+    /// Aerodynamic.
+    pub a: usize,
+    /// Shiny.
+    pub s: usize,
+}
+
+/// The comparison a workflow rule makes.
+#[derive(Debug, Default, Display, PartialEq)]
+pub enum Op {
+    /// No test, always go to the destination.
+    #[default]
+    None,
+    /// Rating must be greater than the test value.
+    Greater,
+    /// Rating must be less than the test value.
+    Less,
+}
+
+/// A single rule inside a named workflow.
+#[derive(Debug, Default)]
+pub struct Workflow {
+    /// Which rating (x, m, a or s) to test. Empty for plain destinations.
+    pub dimension: String,
+    /// The comparison to make.
+    pub op: Op,
+    /// Value to compare the rating against.
+    pub test: usize,
+    /// Where to go if the test passes. "A" accepts and "R" rejects.
+    pub destination: String,
+}
+
+/// The parsed workflows and parts to run through them.
+#[derive(Debug, Default)]
+pub struct System {
+    /// Workflows by name. There's always an "in" workflow to start from.
+    pub workflows: HashMap<String, Vec<Workflow>>,
+    /// All the parts to sort.
+    pub parts: Vec<Part>,
+}
+
+// Ranges of x, m, a and s (in that order).
+type PartRanges = Cuboid<4>;
+
+#[derive(Debug)]
+struct Node<'a> {
+    name: &'a str,
+    part: PartRanges,
+    #[allow(dead_code)]
+    sum: u128, // Just used for debugging
+}
+
+impl Solution for Day19 {
+    const DAY: u32 = 19;
+    type Input = System;
+    type Output = usize;
+
+    #[allow(clippy::too_many_lines)]
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let blocks = aoc_common::numbered_blocks(input);
+        if blocks.len() != 2 {
+            return Err(ParseError::new(
+                1,
+                1,
+                "",
+                "input must be workflows and parts separated by a blank line",
+            )
+            .into());
+        }
+
+        let mut parts = vec![];
+        let mut workflows = HashMap::new();
+        // Every destination seen along with which workflow sends there and
+        // where so they can be checked once all the workflows are known.
+        let mut destinations = vec![];
+
+        for (line_num, line) in &blocks[0] {
+            let line_num = *line_num;
+            // Workflows are a bit terser.
+            // They have a name and then 1..N parts describing a path to work on and a condition for passing that way.
+            //
+            // e.g. - px{a<2006:qkq,m>2090:A,rfg}
+            //
+            // This is the px rule. If a is less than 2006 move to qkq. Otherwise if m is greater than 2090 accept the part.
+            // Finally just go to rfg.
+            // This can be N long but only one "default" at the end which is "A" (accept), "R" (reject) or a rule name.
+            let Some((name, rules)) = line.strip_suffix('}').and_then(|l| l.split_once('{')) else {
+                return Err(ParseError::line(line_num, line, "expected name{rules}").into());
+            };
+
+            let mut flows = vec![];
+            for r in rules.split(',') {
+                let mut workflow = Workflow::default();
+                // No : means it's just a destination rule
+                let Some((test, dest)) = r.split_once(':') else {
+                    workflow.destination = r.to_string();
+                    destinations.push((name, line_num, *line, r));
+                    flows.push(workflow);
+                    continue;
+                };
+                // a<2006:qkq
+                let dimension = test.get(0..1).unwrap_or_default();
+                if !matches!(dimension, "x" | "m" | "a" | "s") {
+                    return Err(ParseError::at(
+                        line_num,
+                        line,
+                        test,
+                        "rating must be x, m, a or s",
+                    )
+                    .into());
+                }
+                workflow.dimension = dimension.to_string();
+                workflow.destination = dest.to_string();
+                destinations.push((name, line_num, *line, dest));
+                workflow.op = match test.get(1..2) {
+                    Some("<") => Op::Less,
+                    Some(">") => Op::Greater,
+                    _ => return Err(ParseError::at(line_num, line, test, "expected < or >").into()),
+                };
+                workflow.test = parse::number(line_num, line, &test[2..])?;
+                flows.push(workflow);
+            }
+            // Otherwise a part could pass every test and have nowhere to go.
+            if flows.last().is_some_and(|w| w.op != Op::None) {
+                let last = rules.rsplit(',').next().unwrap_or(rules);
+                return Err(ParseError::at(
+                    line_num,
+                    line,
+                    last,
+                    "last rule must be a plain destination",
+                )
+                .into());
+            }
+            if workflows.insert(name.to_string(), flows).is_some() {
+                return Err(ParseError::at(line_num, line, name, "workflow already exists").into());
+            }
+        }
+
+        // Make sure every destination exists so evaluating never falls off into
+        // an unknown workflow.
+        let mut rules = HashMap::<_, Vec<_>>::new();
+        for (name, line_num, line, dest) in destinations {
+            if dest != "A" && dest != "R" && !workflows.contains_key(dest) {
+                return Err(ParseError::at(line_num, line, dest, "unknown workflow").into());
+            }
+            rules.entry(name).or_default().push((line_num, line, dest));
+        }
+
+        // Parts are simpler to parse. Just take each entry and split it up.
+        //
+        // e.g. - {x=787,m=2655,a=1222,s=2876}
+        for (line_num, line) in &blocks[1] {
+            let line_num = *line_num;
+            let Some(l) = line.strip_prefix('{').and_then(|l| l.strip_suffix('}')) else {
+                return Err(ParseError::line(line_num, line, "expected {x=N,m=N,a=N,s=N}").into());
+            };
+            let ps = l.split(',').collect::<Vec<_>>();
+            if ps.len() != 4 {
+                return Err(ParseError::line(line_num, line, "expected 4 ratings").into());
+            }
+            let mut part = Part::default();
+            for pt in ps {
+                let Some((rating, v)) = pt.split_once('=') else {
+                    return Err(ParseError::at(line_num, line, pt, "expected rating=N").into());
+                };
+                let v = parse::number(line_num, line, v)?;
+                match rating {
+                    "x" => part.x = v,
+                    "m" => part.m = v,
+                    "a" => part.a = v,
+                    "s" => part.s = v,
+                    _ => {
+                        return Err(ParseError::at(
+                            line_num,
+                            line,
+                            rating,
+                            "rating must be x, m, a or s",
+                        )
+                        .into())
+                    }
+                }
+            }
+            parts.push(part);
+        }
+
+        // Make sure this has the start key.
+        if !workflows.contains_key("in") {
+            return Err(
+                ParseError::new(blocks[0][0].0, 1, "", "no 'in' workflow to start from").into(),
+            );
+        }
+
+        // Or going round in circles which would never accept or reject.
+        if let Some((line_num, line, dest)) =
+            cycle("in", &rules, &mut HashSet::new(), &mut HashSet::new())
+        {
+            return Err(
+                ParseError::at(line_num, line, dest, "workflows go round in a loop").into(),
+            );
+        }
+
+        let system = System { workflows, parts };
+
+        // Dump out the input for debugging.
+        for (k, v) in &system.workflows {
+            debug!("workflow {k} -> {v:?}");
+        }
+        for p in &system.parts {
+            debug!("part {p:?}");
+        }
+
+        Ok(system)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<usize> {
+        // For part1 it's easy. Loop over each part and if it's acceptable add up
+        // it's component values to the overall sum.
+        let mut sum = 0;
+        for p in &input.parts {
+            if acceptable(p, &input.workflows)? {
+                sum += p.x + p.m + p.a + p.s;
+            }
+        }
+        Ok(sum)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<usize> {
+        let all = combinations(input, Interval::new(1, 4001))?;
+        Ok(usize::try_from(all)?)
+    }
+}
+
+/// How many parts with every rating in `ratings` the workflows accept.
+///
+/// Part 2 has 4000 of each rating. See [`enumerate`] for the simple version.
+///
+/// # Errors
+/// If a rule tests a value too big for the ranges or sends parts to a
+/// workflow which doesn't exist.
+#[allow(clippy::too_many_lines)]
+pub fn combinations(input: &System, ratings: Interval) -> Result<u128> {
+    // Part2 gets a lot more complex as we now have a bin of 4000*4000*4000*4000 parts
+    // and want to know based just on our workflows which of those would be
+    // valid to accept.
+    //
+    // Numbers alone tell us we can't run acceptable() above as that would take
+    // months/years to compute even distributed (or cost a fortune).
+    //
+    // But...we don't need all of that. In the end create a tree showing all the
+    // rule destinations. Along each step we track the valid range of parts that let us progress
+    // and adjusting it for each step down.
+    //
+    // i.e. for the sample rules:
+    //
+    // px{a<2006:qkq,m>2090:A,rfg}
+    // pv{a>1716:R,A}
+    // lnx{m>1548:A,A}
+    // rfg{s<537:gd,x>2440:R,A}
+    // qs{s>3448:A,lnx}
+    // qkq{x<1416:A,crn}
+    // crn{x>2662:A,R}
+    // in{s<1351:px,qqz}
+    // qqz{s>2770:qs,m<1801:hdj,R}
+    // gd{a>3333:R,R}
+    // hdj{m>838:A,pv
+    //
+    // Moving from in->px->A requires
+    // s=1..1351,a=2006..4001,m=2091..4001,x=1..4001
+    //
+    // The s is obvious at that's the in rule for it. But for a we have to make sure
+    // and invert the condition which would have passed to qkq when we continue along to m for testing.
+
+    // Create a tree rooted at in and retain it's id.
+    // That tree has a node which has the current name, a part range showing current valid ranges to get there
+    // and a sum of those (for debugging).
+    // Create a worklist of (parent_id, workflows) where workflows is the vector of all workflows attached to that
+    // node (i.e. for "in" we have 2 for destination px and qqz)
+    // Then start a loop while the worklist still has items.
+    //
+    // Pop the top item and make a copy of it's part range as it will shrink for each workflow processed.
+    // Then loop over all the workflows and split the range at the test. The half which passes goes on
+    // to the destination and the half which fails carries on to the next workflow.
+    // Anything that isn't terminal (A/R) gets new work pushed onto the main work list as we add new nodes to the tree.
+    // Effectively building this breadth first.
+    let cur_part = PartRanges::cube(ratings);
+    let mut tree = TreeBuilder::new()
+        .with_root(Node {
+            name: "in",
+            sum: cur_part.volume(),
+            part: cur_part,
+        })
+        .build();
+
+    let root_id = tree.root_id().ok_or_else(|| eyre!("empty tree"))?;
+    let mut cur_id = root_id;
+    let mut work = vec![];
+    work.push((cur_id, workflow(&input.workflows, "in")?));
+
+    while let Some(w) = work.pop() {
+        // Get the parent node we need to attach onto.
+        let mut e = tree
+            .get_mut(w.0)
+            .ok_or_else(|| eyre!("missing tree node"))?;
+
+        // For each workflow compute the part range needed to get to it and
+        // what's left for the following ones.
+        let mut p = e.data().part;
+        for wf in w.1 {
+            let (pass, fail) = match (&wf.op, axis(&wf.dimension)) {
+                // Intervals are half open so > splits just past the test value.
+                (Op::Greater, Some(a)) => {
+                    let past = i64::try_from(wf.test)?
+                        .checked_add(1)
+                        .ok_or_else(|| eyre!("{} is too big to test against", wf.test))?;
+                    let (lo, hi) = p.split_at(a, past);
+                    (hi, lo)
+                }
+                (Op::Less, Some(a)) => p.split_at(a, i64::try_from(wf.test)?),
+                _ => (p, p),
+            };
+
+            // Make a new node, record it's id.
+            cur_id = e
+                .append(Node {
+                    name: &wf.destination,
+                    sum: pass.volume(),
+                    part: pass,
+                })
+                .node_id();
+
+            // Now adjust for next loop (assuming non-terminal) and set p now
+            // to the state if the test failed to match. This doesn't matter if
+            // we're on the last stage of the loop but doesn't hurt.
+            p = fail;
+
+            // These are terminal so no more work to push on. Otherwise it refers
+            // to another flow so push that id in as well as all the workflows for that node.
+            if wf.destination != "R" && wf.destination != "A" {
+                work.push((cur_id, workflow(&input.workflows, &wf.destination)?));
+            }
+        }
+    }
+
+    // Debug print the whole tree. This is where sum helps since it's each to see
+    // the decreases at each level and hand verify if needed (certainly helped me debug).
+    if enabled!(Level::DEBUG) {
+        let mut s = String::new();
+        tree.write_formatted(&mut s)?;
+        debug!("tree:\n{s}");
+    }
+
+    // Traverse the tree from the bottom and find the "A" nodes (nothing else matters).
+    // Those nodes will have the values we want to add to our sum.
+    let mut sum = 0;
+    let root = tree.root().ok_or_else(|| eyre!("empty tree"))?;
+    for node in root.traverse_pre_order() {
+        let n = node.data();
+        // Only find the ends which end in an accept.
+        if n.name != "A" {
+            continue;
+        }
+        let p = &n.part;
+        let mini = p.volume();
+
+        if enabled!(Level::TRACE) {
+            // For debugging walk back up, find the parents to make a list of the path to get here.
+            // Then reverse it so we can pretty print it out.
+            let mut entries = vec!["A"];
+            for a in node.ancestors() {
+                entries.push(a.data().name);
+            }
+            entries.reverse();
+            trace!("{} = {p:?} - {mini}", entries.join(" -> "));
+        }
+
+        sum += mini;
+    }
+    Ok(sum)
+}
+
+/// Slow reference for [`combinations`] which runs every part with ratings
+/// in `ratings` through the workflows one at a time. Only practical for a
+/// handful of ratings.
+///
+/// # Errors
+/// As [`acceptable`] does.
+pub fn enumerate(input: &System, ratings: Interval) -> Result<u128> {
+    let values = || (ratings.start..ratings.end).filter_map(|v| usize::try_from(v).ok());
+    let mut sum = 0;
+    for x in values() {
+        for m in values() {
+            for a in values() {
+                for s in values() {
+                    if acceptable(&Part { x, m, a, s }, &input.workflows)? {
+                        sum += 1;
+                    }
+                }
+            }
+        }
+    }
+    Ok(sum)
+}
+
+// Which axis of PartRanges a rating is.
+fn axis(dimension: &str) -> Option<usize> {
+    match dimension {
+        "x" => Some(0),
+        "m" => Some(1),
+        "a" => Some(2),
+        "s" => Some(3),
+        _ => None,
+    }
+}
+
+// Depth first from `name` looking for a rule which sends parts back to a
+// workflow still being followed in `path`. `done` is every workflow already
+// known not to lead into a loop.
+fn cycle<'a>(
+    name: &'a str,
+    rules: &HashMap<&'a str, Vec<(usize, &'a str, &'a str)>>,
+    path: &mut HashSet<&'a str>,
+    done: &mut HashSet<&'a str>,
+) -> Option<(usize, &'a str, &'a str)> {
+    path.insert(name);
+    for &(line_num, line, dest) in rules.get(name).into_iter().flatten() {
+        if path.contains(dest) {
+            return Some((line_num, line, dest));
+        }
+        if !done.contains(dest) {
+            if let Some(c) = cycle(dest, rules, path, done) {
+                return Some(c);
+            }
+        }
+    }
+    path.remove(name);
+    done.insert(name);
+    None
+}
+
+// The rules for workflow `name`.
+fn workflow<'a>(
+    workflows: &'a HashMap<String, Vec<Workflow>>,
+    name: &str,
+) -> Result<&'a Vec<Workflow>> {
+    workflows
+        .get(name)
+        .ok_or_else(|| eyre!("no workflow named {name}"))
+}
+
+/// Return true if the given part after the workflow run is acceptable.
+///
+/// # Errors
+/// If the workflows refer to a flow which doesn't exist or never reach a
+/// decision. Parsing makes sure neither can happen.
+#[allow(clippy::implicit_hasher)]
+pub fn acceptable(part: &Part, workflows: &HashMap<String, Vec<Workflow>>) -> Result<bool> {
+    let mut cur = workflow(workflows, "in")?;
+    // Every workflow followed so far. Coming back to one would go round
+    // forever.
+    let mut seen = HashSet::from(["in"]);
+    'outer: loop {
+        for w in cur {
+            let mut check_dest = false;
+            match w.op {
+                Op::None => {
+                    // No op means this is just a destination which could be reject, accept or a new flow to start.
+                    check_dest = true;
+                }
+                Op::Greater | Op::Less => {
+                    let p = match w.dimension.as_str() {
+                        "x" => part.x,
+                        "m" => part.m,
+                        "a" => part.a,
+                        "s" => part.s,
+                        _ => return Err(eyre!("invalid dimension {}", w.dimension)),
+                    };
+                    if w.op == Op::Greater && p > w.test {
+                        check_dest = true;
+                    }
+                    if w.op == Op::Less && p < w.test {
+                        check_dest = true;
+                    }
+                }
+            }
+            if check_dest {
+                match w.destination.as_str() {
+                    "R" => return Ok(false),
+                    "A" => return Ok(true),
+                    dest => {
+                        if !seen.insert(dest) {
+                            return Err(eyre!("workflows loop back to {dest}"));
+                        }
+                        cur = workflow(workflows, dest)?;
+                        continue 'outer;
+                    }
+                }
+            }
+        }
+        break;
+    }
+    Err(eyre!("workflows fell off the end"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_bad_rule() {
+        let err = Day19
+            .parse("in{s<1351:A,q>5:R,A}\n\n{x=787,m=2655,a=1222,s=2876}\n")
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (1, 13, "q>5"));
+    }
+
+    #[test]
+    fn parse_dead_ends() {
+        let parse = |text| {
+            Day19
+                .parse(text)
+                .unwrap_err()
+                .downcast::<ParseError>()
+                .unwrap()
+        };
+        // Parts with x of 5 or more have nowhere to go.
+        let err = parse("in{x<5:A}\n\n{x=9,m=1,a=1,s=1}\n");
+        assert_eq!((err.line, err.column, err.token.as_str()), (1, 4, "x<5:A"));
+
+        let err = parse("in{x<5:A,in}\n\n{x=9,m=1,a=1,s=1}\n");
+        assert_eq!((err.line, err.column, err.token.as_str()), (1, 10, "in"));
+
+        // Loops only matter when they can be reached.
+        let err = parse("qs{lnx}\nin{x<5:A,qqz}\nlnx{m>1:R,qs}\nqqz{lnx}\n\n{x=9,m=1,a=1,s=1}\n");
+        assert_eq!((err.line, err.column, err.token.as_str()), (1, 4, "lnx"));
+        assert!(Day19.parse("in{A}\nqs{qs}\n\n{x=9,m=1,a=1,s=1}\n").is_ok());
+    }
+
+    #[test]
+    fn huge_test() -> Result<()> {
+        let input = Day19.parse("in{x>9223372036854775807:R,A}\n\n{x=1,m=1,a=1,s=1}\n")?;
+        assert!(Day19.part2(&input).is_err());
+        Ok(())
+    }
+}
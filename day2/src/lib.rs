@@ -0,0 +1,596 @@
+//! day2 advent 20XX
+use aoc_common::output::{self, Align, Format};
+use aoc_common::{parse, ParseError, Solution};
+use color_eyre::eyre::{Result, WrapErr};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Cube conundrum.
+#[derive(Debug, Default)]
+pub struct Day2 {
+    /// Bag contents to check games against for part1.
+    pub options: Options,
+}
+
+/// How many of each colour are in the bag.
+#[derive(Clone, Debug, Default, clap::Args)]
+#[group(skip)]
+pub struct Options {
+    /// Red cubes in the bag. Same as --limit red=N.
+    #[arg(long, value_name = "N")]
+    pub red: Option<usize>,
+
+    /// Green cubes in the bag. Same as --limit green=N.
+    #[arg(long, value_name = "N")]
+    pub green: Option<usize>,
+
+    /// Blue cubes in the bag. Same as --limit blue=N.
+    #[arg(long, value_name = "N")]
+    pub blue: Option<usize>,
+
+    /// How many cubes of a colour the bag holds. Can be given more than
+    /// once. These change the bag from --bag (or 12 red, 13 green and 14
+    /// blue without it) so --limit red=0 takes red out. Any colour in
+    /// neither has none.
+    #[arg(long = "limit", value_name = "COLOUR=N", value_parser = parse_limit)]
+    pub limits: Vec<(String, usize)>,
+
+    /// TOML file with how many of each colour the bag holds (like
+    /// `red = 12`). This replaces the 12 red, 13 green and 14 blue bag
+    /// rather than adding to it.
+    #[arg(long, value_name = "FILE")]
+    pub bag: Option<PathBuf>,
+}
+
+impl Options {
+    /// The bag from the file (or the puzzle's 12 red, 13 green and 14 blue)
+    /// with the command line limits applied on top.
+    ///
+    /// # Errors
+    /// If the bag file can't be read or parsed.
+    pub fn bag(&self) -> Result<Cubes> {
+        let mut bag = match &self.bag {
+            Some(path) => {
+                let text = fs::read_to_string(path)
+                    .wrap_err_with(|| format!("can't read {}", path.display()))?;
+                toml::from_str(&text).wrap_err_with(|| format!("can't parse {}", path.display()))?
+            }
+            None => Cubes::from([
+                ("red".to_string(), 12),
+                ("green".to_string(), 13),
+                ("blue".to_string(), 14),
+            ]),
+        };
+        for (colour, n) in [
+            ("red", self.red),
+            ("green", self.green),
+            ("blue", self.blue),
+        ] {
+            if let Some(n) = n {
+                bag.insert(colour.to_string(), n);
+            }
+        }
+        bag.extend(self.limits.iter().cloned());
+        Ok(bag)
+    }
+}
+
+fn parse_limit(s: &str) -> Result<(String, usize), String> {
+    let (colour, n) = s
+        .split_once('=')
+        .ok_or_else(|| format!("{s}: expected COLOUR=N"))?;
+    let n = n.parse::<usize>().map_err(|e| format!("{s}: {e}"))?;
+    Ok((colour.to_string(), n))
+}
+
+/// How many cubes of each colour. Any colour not there has none.
+pub type Cubes = BTreeMap<String, usize>;
+
+/// One handful of cubes shown from the bag.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Draw {
+    /// How many of each colour were shown.
+    pub cubes: Cubes,
+}
+
+impl Draw {
+    /// Whether this many of each colour could have come out of `bag`.
+    #[must_use]
+    pub fn fits(&self, bag: &Cubes) -> bool {
+        self.cubes
+            .iter()
+            .all(|(c, n)| *n <= bag.get(c).copied().unwrap_or(0))
+    }
+}
+
+/// A game and every draw in it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Game {
+    /// Game number.
+    pub id: usize,
+    /// The draws in order.
+    pub draws: Vec<Draw>,
+}
+
+impl Game {
+    /// The most of each colour seen in any one draw. That's also the fewest
+    /// the bag could have held for this game to happen.
+    #[must_use]
+    pub fn minimum(&self) -> Cubes {
+        let mut min = Cubes::new();
+        for (c, n) in self.draws.iter().flat_map(|d| &d.cubes) {
+            let m = min.entry(c.clone()).or_insert(0);
+            *m = (*m).max(*n);
+        }
+        min
+    }
+
+    /// Whether every draw could have come out of `bag`.
+    #[must_use]
+    pub fn possible(&self, bag: &Cubes) -> bool {
+        self.draws.iter().all(|d| d.fits(bag))
+    }
+
+    // Input looks like:
+    //
+    // Game X: A blue, B red; A red, B green, C blue; A green
+    //
+    // i.e. a game with a number then a variable number of draws separated by
+    // ; where each draw is a number and a colour for any number of colours
+    // separated by ,
+    fn parse(line_num: usize, line: &str) -> Result<Self, ParseError> {
+        // Basic line validation. Everything is Game N: followed by the draws.
+        let Some((head, rem)) = line.split_once(':') else {
+            return Err(ParseError::line(line_num, line, "missing :"));
+        };
+        let parts = head.split_whitespace().collect::<Vec<_>>();
+        if parts.len() != 2 || parts[0] != "Game" {
+            return Err(ParseError::at(line_num, line, head, "expected Game N"));
+        }
+
+        let mut game = Game {
+            id: parse::number(line_num, line, parts[1])?,
+            draws: vec![],
+        };
+        for d in rem.split(';') {
+            let mut draw = Draw::default();
+            for cube in d.split(',') {
+                // Trailing , or an empty game. Nothing drawn either way.
+                if cube.trim().is_empty() {
+                    continue;
+                }
+                let [num, colour] = cube.split_whitespace().collect::<Vec<_>>()[..] else {
+                    return Err(ParseError::at(
+                        line_num,
+                        line,
+                        cube.trim(),
+                        "expected N COLOUR",
+                    ));
+                };
+                let num = parse::number::<usize>(line_num, line, num)?;
+                if draw.cubes.insert(colour.to_string(), num).is_some() {
+                    return Err(ParseError::at(line_num, line, colour, "colour drawn twice"));
+                }
+            }
+            game.draws.push(draw);
+        }
+        Ok(game)
+    }
+}
+
+impl FromStr for Game {
+    type Err = ParseError;
+
+    // A single line like `Game 3: 1 red, 2 blue; 4 purple`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Game::parse(1, s)
+    }
+}
+
+/// What the games say about the one bag they were all drawn from.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Inference {
+    /// The smallest bag every game could have come from.
+    pub minimum: Cubes,
+    /// The bag the games most likely came from.
+    pub likeliest: Cubes,
+    /// Log likelihood of the games being drawn from `likeliest`.
+    pub log_likelihood: f64,
+    /// Whether `likeliest` is as big as was allowed. A bigger bag (with
+    /// about the same mix) could be likelier still.
+    pub capped: bool,
+    /// The bag the games were checked against.
+    pub bag: Cubes,
+    /// Log likelihood of the games being drawn from `bag`. None when some
+    /// couldn't have been.
+    pub bag_log_likelihood: Option<f64>,
+    /// Games which couldn't have come from `bag`.
+    pub conflicts: Vec<Conflict>,
+}
+
+/// A game which couldn't have come from a bag.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Conflict {
+    /// Game number.
+    pub game: usize,
+    /// Each colour the bag doesn't hold enough of with the most drawn at
+    /// once.
+    pub over: Cubes,
+}
+
+/// Everything `games` say about the bag they came from compared to `bag`.
+/// Bags with more than `max_cubes` in them aren't considered for the
+/// likeliest (unless the minimum is already bigger).
+#[must_use]
+pub fn infer(games: &[Game], bag: &Cubes, max_cubes: usize) -> Inference {
+    let likeliest = likeliest(games, max_cubes);
+    let minimum = minimum(games);
+    let size = |b: &Cubes| b.values().sum::<usize>();
+    let conflicts = games
+        .iter()
+        .filter_map(|g| {
+            let over = g
+                .minimum()
+                .into_iter()
+                .filter(|(c, n)| *n > bag.get(c).copied().unwrap_or(0))
+                .collect::<Cubes>();
+            (!over.is_empty()).then_some(Conflict { game: g.id, over })
+        })
+        .collect();
+    Inference {
+        log_likelihood: log_likelihood(games, &likeliest).unwrap_or(f64::NEG_INFINITY),
+        capped: size(&likeliest) > size(&minimum) && size(&likeliest) >= max_cubes,
+        minimum,
+        likeliest,
+        bag: bag.clone(),
+        bag_log_likelihood: log_likelihood(games, bag),
+        conflicts,
+    }
+}
+
+/// The smallest bag every game could have come from. The most of each
+/// colour seen in any one draw.
+#[must_use]
+pub fn minimum(games: &[Game]) -> Cubes {
+    let mut min = Cubes::new();
+    for (c, n) in games.iter().flat_map(Game::minimum) {
+        let m = min.entry(c).or_insert(0);
+        *m = (*m).max(n);
+    }
+    min
+}
+
+// ln(n choose k).
+#[allow(clippy::cast_precision_loss)]
+fn ln_choose(n: usize, k: usize) -> f64 {
+    (0..k).map(|i| ((n - i) as f64 / (i + 1) as f64).ln()).sum()
+}
+
+/// Log likelihood of every draw in `games` coming from `bag`. Each draw is
+/// a handful taken without replacement (so a multivariate hypergeometric)
+/// which goes back in the bag before the next. None if any draw couldn't
+/// have come from `bag` at all.
+#[must_use]
+pub fn log_likelihood(games: &[Game], bag: &Cubes) -> Option<f64> {
+    let total = bag.values().sum::<usize>();
+    let mut l = 0.0;
+    for d in games.iter().flat_map(|g| &g.draws) {
+        if !d.fits(bag) {
+            return None;
+        }
+        for (c, k) in &d.cubes {
+            l += ln_choose(bag.get(c).copied().unwrap_or(0), *k);
+        }
+        l -= ln_choose(total, d.cubes.values().sum());
+    }
+    Some(l)
+}
+
+/// The bag with no more than `max_cubes` in it (or the minimum if that's
+/// bigger) which `games` most likely came from. See [`log_likelihood`].
+/// Where bags tie the smallest wins.
+///
+/// For a given number of cubes in the bag the likelihood is a sum over
+/// colours of terms which each gain less for every cube added. So the best
+/// bag of each size is the best one a cube smaller plus a cube of whichever
+/// colour gains most. Walking sizes up from the minimum that way finds the
+/// best bag of every size in one pass.
+#[must_use]
+pub fn likeliest(games: &[Game], max_cubes: usize) -> Cubes {
+    let draws = games.iter().flat_map(|g| &g.draws).collect::<Vec<_>>();
+    let gain = |c: &str, n: usize| -> f64 {
+        draws
+            .iter()
+            .filter_map(|d| d.cubes.get(c))
+            .map(|k| ln_choose(n + 1, *k) - ln_choose(n, *k))
+            .sum()
+    };
+
+    let mut bag = minimum(games);
+    let mut best = (log_likelihood(games, &bag), bag.clone());
+    for _ in bag.values().sum::<usize>()..max_cubes {
+        let Some((_, n)) = bag
+            .iter_mut()
+            .map(|(c, n)| (gain(c, *n), n))
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+        else {
+            break;
+        };
+        *n += 1;
+        let l = log_likelihood(games, &bag);
+        if l > best.0 {
+            best = (l, bag.clone());
+        }
+    }
+    best.1
+}
+
+// Like `12 blue, 3 purple`.
+fn show(cubes: &Cubes) -> String {
+    if cubes.is_empty() {
+        return "nothing".to_string();
+    }
+    let parts = cubes
+        .iter()
+        .map(|(c, n)| format!("{n} {c}"))
+        .collect::<Vec<_>>();
+    parts.join(", ")
+}
+
+/// Write `inference` in `format`. Text is a summary with a table of the
+/// conflicting games.
+///
+/// # Errors
+/// If `w` can't be written.
+pub fn write_inference(format: Format, inference: &Inference, w: &mut impl Write) -> Result<()> {
+    let i = inference;
+    match format {
+        Format::Text => {
+            let ll = |l: Option<f64>| {
+                l.map_or_else(
+                    || "impossible".to_string(),
+                    |l| format!("log likelihood {l:.3}"),
+                )
+            };
+            let capped = if i.capped {
+                ", raise --max-cubes to look at bigger bags"
+            } else {
+                ""
+            };
+            writeln!(w, "Minimum bag:   {}", show(&i.minimum))?;
+            writeln!(
+                w,
+                "Likeliest bag: {} ({}{capped})",
+                show(&i.likeliest),
+                ll(Some(i.log_likelihood))
+            )?;
+            writeln!(
+                w,
+                "Given bag:     {} ({})",
+                show(&i.bag),
+                ll(i.bag_log_likelihood)
+            )?;
+            writeln!(w)?;
+            if i.conflicts.is_empty() {
+                writeln!(w, "Every game could have come from the given bag.")?;
+                return Ok(());
+            }
+            let rows = i
+                .conflicts
+                .iter()
+                .flat_map(|c| {
+                    c.over.iter().map(|(colour, n)| {
+                        vec![
+                            c.game.to_string(),
+                            colour.clone(),
+                            n.to_string(),
+                            i.bag.get(colour).copied().unwrap_or(0).to_string(),
+                        ]
+                    })
+                })
+                .collect::<Vec<_>>();
+            output::table(
+                w,
+                &[
+                    ("Game", Align::Right),
+                    ("Colour", Align::Left),
+                    ("Drawn", Align::Right),
+                    ("Bag", Align::Right),
+                ],
+                &rows,
+            )?;
+        }
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut *w, inference)?;
+            writeln!(w)?;
+        }
+        Format::Tsv => {
+            // One row per colour in each bag and per colour a game drew too
+            // many of.
+            writeln!(w, "bag\tgame\tcolour\tcubes")?;
+            for (name, bag) in [
+                ("minimum", &i.minimum),
+                ("likeliest", &i.likeliest),
+                ("given", &i.bag),
+            ] {
+                for (c, n) in bag {
+                    writeln!(w, "{name}\t\t{c}\t{n}")?;
+                }
+            }
+            for c in &i.conflicts {
+                for (colour, n) in &c.over {
+                    writeln!(w, "conflict\t{}\t{colour}\t{n}", c.game)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+    type Input = Vec<Game>;
+    type Output = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        Ok(input
+            .lines()
+            .enumerate()
+            .map(|(n, line)| Game::parse(n + 1, line))
+            .collect::<Result<_, _>>()?)
+    }
+
+    // Add up game numbers for games which never drew more of a colour than
+    // the bag holds.
+    fn part1(&self, input: &Self::Input) -> Result<usize> {
+        let bag = self.options.bag()?;
+        Ok(input
+            .iter()
+            .filter(|g| g.possible(&bag))
+            .map(|g| g.id)
+            .sum())
+    }
+
+    // Reverse things. The max seen for each colour is the minimum number of
+    // cubes needed in the bag to make that game valid. Multiply those
+    // together (for every colour in any game) and make a sum of them.
+    //
+    // If there's a game that never put out a colour this will just reduce to
+    // 0 since it needed none of them.
+    fn part2(&self, input: &Self::Input) -> Result<usize> {
+        let colours = input
+            .iter()
+            .flat_map(|g| &g.draws)
+            .flat_map(|d| d.cubes.keys())
+            .collect::<BTreeSet<_>>();
+        Ok(input
+            .iter()
+            .map(|g| {
+                let min = g.minimum();
+                colours
+                    .iter()
+                    .map(|c| min.get(*c).copied().unwrap_or(0))
+                    .product::<usize>()
+            })
+            .sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn parse_errors() {
+        let err = Day2::default()
+            .parse("Game 1: 3 blue\nGame 2: 4 purple, 1; 2 red\n")
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 19, "1"));
+        let err = "Game 1: 3 red, 2 red".parse::<Game>().unwrap_err();
+        assert_eq!(
+            (err.column, err.message.as_str()),
+            (18, "colour drawn twice")
+        );
+    }
+
+    #[test]
+    fn any_colours() -> Result<()> {
+        let game = "Game 7: 3 purple, 1 red; 2 teal, 5 purple".parse::<Game>()?;
+        assert_eq!(game.id, 7);
+        assert_eq!(game.draws.len(), 2);
+        assert_eq!(game.draws[1].cubes["teal"], 2);
+
+        let options = |limits: &[(&str, usize)]| Options {
+            limits: limits.iter().map(|(c, n)| (c.to_string(), *n)).collect(),
+            ..Options::default()
+        };
+        let input = vec![game, "Game 8: 1 red".parse()?];
+        let day = |o| Day2 { options: o };
+        let d = day(options(&[("red", 1), ("purple", 5), ("teal", 2)]));
+        assert_eq!(d.part1(&input)?, 15);
+        // Anything not listed isn't in the bag at all.
+        let d = day(options(&[("red", 1), ("purple", 5)]));
+        assert_eq!(d.part1(&input)?, 8);
+        // Game 8 needs no purple or teal.
+        assert_eq!(d.part2(&input)?, 5 * 2);
+
+        // Limits go on top of the usual bag and --red is the same as one.
+        let o = Options {
+            red: Some(20),
+            ..options(&[("purple", 3), ("blue", 0)])
+        };
+        let expected = [("blue", 0), ("green", 13), ("purple", 3), ("red", 20)];
+        let expected = expected.map(|(c, n)| (c.to_string(), n));
+        assert_eq!(o.bag()?, Cubes::from_iter(expected));
+        let example = Day2::default().parse(EXAMPLE)?;
+        assert_eq!(day(options(&[("purple", 3)])).part1(&example)?, 8);
+
+        assert_eq!(parse_limit("purple=3"), Ok(("purple".to_string(), 3)));
+        assert!(parse_limit("purple").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn inference() -> Result<()> {
+        let day = Day2::default();
+        let games = day.parse(EXAMPLE)?;
+        let i = infer(&games, &day.options.bag()?, 1000);
+        let bag = |c: &[(&str, usize)]| {
+            c.iter()
+                .map(|(c, n)| (c.to_string(), *n))
+                .collect::<Cubes>()
+        };
+        assert_eq!(i.minimum, bag(&[("red", 20), ("green", 13), ("blue", 15)]));
+        let conflicts = i
+            .conflicts
+            .iter()
+            .map(|c| (c.game, c.over.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            conflicts,
+            [
+                (3, bag(&[("red", 20)])),
+                (4, bag(&[("blue", 15), ("red", 14)]))
+            ]
+        );
+        assert_eq!(i.bag_log_likelihood, None);
+        assert!(i.capped);
+        assert!(i.log_likelihood > log_likelihood(&games, &i.minimum).unwrap());
+
+        // 2 red is likelier from 3 red and 1 blue than from 2 and 1. Check
+        // against every small bag.
+        let games = ["Game 1: 2 red; 1 red, 1 blue".parse::<Game>()?];
+        let best = likeliest(&games, 100);
+        assert_eq!(best, bag(&[("red", 3), ("blue", 1)]));
+        let l = log_likelihood(&games, &best).unwrap();
+        for red in 2..10 {
+            for blue in 1..10 {
+                let other = log_likelihood(&games, &bag(&[("red", red), ("blue", blue)])).unwrap();
+                assert!(other <= l);
+            }
+        }
+        // Capped at the minimum.
+        assert_eq!(likeliest(&games, 0), bag(&[("red", 2), ("blue", 1)]));
+
+        let mut out = vec![];
+        write_inference(Format::Text, &infer(&games, &best, 100), &mut out)?;
+        let out = String::from_utf8(out)?;
+        assert!(out.contains("Likeliest bag: 1 blue, 3 red (log likelihood -1.386)"));
+        assert!(out.contains("Every game could"));
+
+        // Drawing none of a colour fits a bag without any.
+        let games = ["Game 1: 0 purple, 3 red".parse::<Game>()?];
+        let i = infer(&games, &bag(&[("red", 3)]), 10);
+        assert_eq!(i.bag_log_likelihood, Some(0.0));
+        assert!(i.conflicts.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+//! day20 advent 2023
+//!
+//! <https://adventofcode.com/2023/day/20>
+use aoc_common::Solution;
+use color_eyre::eyre::{eyre, Result};
+
+/// Not solved yet.
+#[derive(Debug, Default)]
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u32 = 20;
+    type Input = Vec<String>;
+    type Output = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(&self, _input: &Self::Input) -> Result<usize> {
+        Err(eyre!("day20 part1 not implemented"))
+    }
+
+    fn part2(&self, _input: &Self::Input) -> Result<usize> {
+        Err(eyre!("day20 part2 not implemented"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Paste the puzzle's example into example.txt (and example2.txt etc if a
+    // part has its own) and fill in the expected answers.
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    #[ignore = "part1 isn't finished yet"]
+    fn part1_example() -> Result<()> {
+        let day = Day20;
+        let input = day.parse(EXAMPLE)?;
+        assert_eq!(day.part1(&input)?, 0);
+        Ok(())
+    }
+
+    #[test]
+    #[ignore = "part2 isn't finished yet"]
+    fn part2_example() -> Result<()> {
+        let day = Day20;
+        let input = day.parse(EXAMPLE)?;
+        assert_eq!(day.part2(&input)?, 0);
+        Ok(())
+    }
+}
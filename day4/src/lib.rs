@@ -0,0 +1,95 @@
+//! day4 advent 20XX
+use aoc_common::{parse, ParseError, Solution};
+use color_eyre::eyre::Result;
+use std::collections::{HashMap, HashSet};
+
+/// Scratchcards.
+#[derive(Debug, Default)]
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+    /// How many choices on each card were winners.
+    type Input = Vec<u32>;
+    type Output = usize;
+
+    // Input has the form:
+    //
+    // Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+    // ..
+    //
+    // The card number isn't important (it's the line number and the input is in order).
+    // The numbers to the left are the winning numbers and the numbers after the |
+    // are the choices.
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let mut cards = vec![];
+        for (line_num, line) in input.lines().enumerate() {
+            let line_num = line_num + 1;
+            // Everything after Card N: is the numbers.
+            let Some((_, numbers)) = line.split_once(':') else {
+                return Err(ParseError::line(line_num, line, "missing :").into());
+            };
+            let mut winners = HashSet::new();
+            let mut found = 0;
+            let mut parse_done = false;
+            for p in numbers.split_whitespace() {
+                if p == "|" {
+                    parse_done = true;
+                    continue;
+                }
+                let number = parse::number::<usize>(line_num, line, p)?;
+                if parse_done {
+                    if winners.contains(&number) {
+                        found += 1;
+                    }
+                } else {
+                    winners.insert(number);
+                }
+            }
+            if !parse_done {
+                return Err(ParseError::line(line_num, line, "missing |").into());
+            }
+            cards.push(found);
+        }
+        Ok(cards)
+    }
+
+    // Find how many choices are winners and then compute the score here
+    // as 2^(matches -1). i.e. it starts at 1 and just doubles for each
+    // additional match. Find the sum of this.
+    fn part1(&self, input: &Self::Input) -> Result<usize> {
+        Ok(input
+            .iter()
+            .filter(|found| **found > 0)
+            .map(|found| 2_usize.pow(found - 1))
+            .sum())
+    }
+
+    // For the match count generate N more cards. 1 more each for the next N
+    // cards (up to the end of the list). Keep adding them on as you
+    // progress through each card. Each card processed also counts for 1.
+    // At the end sum up the total number of cards generated.
+    fn part2(&self, input: &Self::Input) -> Result<usize> {
+        let mut card_count = HashMap::new();
+        for (card_num, found) in input.iter().enumerate() {
+            // The current card always gets an entry but do this *after* we pull
+            // out the repeat count below so we don't add any extras.
+            let cur = card_num + 1;
+            let repeat = *card_count.get(&cur).unwrap_or(&0);
+            card_count.entry(cur).and_modify(|f| *f += 1).or_insert(1);
+
+            // Now if this card found something loop and add more entries in
+            // the map for the following games that get additional copies.
+            if *found > 0 {
+                for _ in 0..=repeat {
+                    for i in (card_num + 2)..(card_num + 2 + *found as usize) {
+                        if i <= input.len() {
+                            card_count.entry(i).and_modify(|f| *f += 1).or_insert(1);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(card_count.values().sum())
+    }
+}
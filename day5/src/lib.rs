@@ -0,0 +1,197 @@
+//! day5 advent 20XX
+use aoc_common::interval::{Interval, RangeSet};
+use aoc_common::{parse, ParseError, Solution};
+use color_eyre::eyre::{eyre, Result};
+use std::collections::{HashMap, HashSet};
+
+/// Seed fertilizer almanac.
+#[derive(Debug, Default)]
+pub struct Day5;
+
+/// The parsed almanac.
+#[derive(Clone, Debug, Default)]
+pub struct Almanac {
+    /// Seed numbers from the first line.
+    pub seeds: Vec<i64>,
+    /// Map of source category to its destination category and the ranges
+    /// translating between them.
+    pub maps: HashMap<String, (String, Vec<Mapping>)>,
+}
+
+/// One line of a map. Values in `src` move by `offset` and anything not in
+/// any line of the map stays the same.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Mapping {
+    /// Source values covered.
+    pub src: Interval,
+    /// Destination minus source.
+    pub offset: i64,
+}
+
+impl Solution for Day5 {
+    const DAY: u32 = 5;
+    type Input = Almanac;
+    type Output = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let blocks = aoc_common::numbered_blocks(input);
+        let mut maps = HashMap::new();
+
+        // Just parse seeds direct and then start parsing.
+        let Some(first) = blocks.first() else {
+            return Err(ParseError::new(1, 1, "", "empty input").into());
+        };
+        let (line_num, line) = first[0];
+        let Some(seeds) = line.strip_prefix("seeds:") else {
+            return Err(ParseError::line(line_num, line, "expected 'seeds: '").into());
+        };
+        if first.len() != 1 {
+            let (n, l) = first[1];
+            return Err(ParseError::line(n, l, "seeds must be followed by a blank line").into());
+        }
+        let tokens = seeds.split_whitespace().collect::<Vec<_>>();
+        // Part2 reads these as start/length pairs so one can't be left over.
+        if tokens.len() % 2 != 0 {
+            let last = tokens.last().copied().unwrap_or(seeds);
+            return Err(ParseError::at(line_num, line, last, "seeds must come in pairs").into());
+        }
+        let seeds = tokens
+            .iter()
+            .map(|f| parse::number(line_num, line, f))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Where each map's destination is named so the chain of them can be
+        // checked once they're all known.
+        let mut headers = HashMap::new();
+
+        // Each remaining block is a header line naming the src/dest followed by
+        // the data lines for that map.
+        for block in &blocks[1..] {
+            let (line_num, line) = block[0];
+            let parts = line.split_whitespace().collect::<Vec<_>>();
+            let sub = parts[0].split('-').collect::<Vec<_>>();
+            if parts.len() != 2 || parts[1] != "map:" || sub.len() != 3 || sub[1] != "to" {
+                return Err(ParseError::line(line_num, line, "expected 'src-to-dest map:'").into());
+            }
+            let src = sub[0];
+            let dest = sub[2];
+
+            let mut map = vec![];
+            for (line_num, line) in &block[1..] {
+                let parts = line.split_whitespace().collect::<Vec<_>>();
+                if parts.len() != 3 {
+                    return Err(ParseError::line(*line_num, line, "expected 3 numbers").into());
+                }
+                let nums = parts
+                    .iter()
+                    .map(|f| parse::number::<i64>(*line_num, line, f))
+                    .collect::<Result<Vec<_>, _>>()?;
+                // dest src len
+                map.push(Mapping {
+                    src: Interval::with_len(nums[1], nums[2]),
+                    offset: nums[0] - nums[1],
+                });
+            }
+            if maps
+                .insert(src.to_string(), (dest.to_string(), map))
+                .is_some()
+            {
+                return Err(ParseError::at(line_num, line, src, "already mapped from").into());
+            }
+            headers.insert(src, (line_num, line, dest));
+        }
+
+        // The maps have to lead from seed to location without going round in
+        // circles.
+        let mut cur = "seed";
+        let mut seen = HashSet::from([cur]);
+        // Where cur was named.
+        let mut named = (line_num, line, &line[..4]);
+        while cur != "location" {
+            let Some(&(line_num, line, dest)) = headers.get(cur) else {
+                let (line_num, line, token) = named;
+                let message = format!("nothing maps {cur} on towards location");
+                return Err(ParseError::at(line_num, line, token, &message).into());
+            };
+            if !seen.insert(dest) {
+                return Err(ParseError::at(line_num, line, dest, "maps loop back to").into());
+            }
+            cur = dest;
+            named = (line_num, line, dest);
+        }
+        Ok(Almanac { seeds, maps })
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<usize> {
+        let seeds = input.seeds.iter().map(|s| Interval::with_len(*s, 1));
+        lowest(&seeds.collect(), &input.maps)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<usize> {
+        // Seeds are really start/length pairs. Far too many to do one at a time
+        // but whole ranges can go through each map together.
+        let seeds = input
+            .seeds
+            .chunks_exact(2)
+            .map(|c| Interval::with_len(c[0], c[1]));
+        lowest(&seeds.collect(), &input.maps)
+    }
+}
+
+// Push every seed through the maps and return the lowest location.
+fn lowest(seeds: &RangeSet, maps: &HashMap<String, (String, Vec<Mapping>)>) -> Result<usize> {
+    let mut cur = "seed";
+    let mut ids = seeds.clone();
+    while cur != "location" {
+        let (dest, map) = maps
+            .get(cur)
+            .ok_or_else(|| eyre!("nothing maps {cur} on towards location"))?;
+        ids = apply(&ids, map);
+        cur = dest;
+    }
+    let min = ids.min().ok_or_else(|| eyre!("no seeds"))?;
+    Ok(usize::try_from(min)?)
+}
+
+// Move every id covered by a line of the map and keep the rest as is.
+fn apply(ids: &RangeSet, map: &[Mapping]) -> RangeSet {
+    let mut moved = RangeSet::new();
+    let mut left = ids.clone();
+    for m in map {
+        let src = RangeSet::from(m.src);
+        moved = moved.union(&left.intersection(&src).shift(m.offset));
+        left = left.difference(&src);
+    }
+    moved.union(&left)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn odd_seeds() {
+        let err = Day5
+            .parse("seeds: 79 14 55\n\nseed-to-soil map:\n50 98 2\n")
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (1, 14, "55"));
+    }
+
+    #[test]
+    fn broken_chain() {
+        let parse = |text| {
+            Day5.parse(text)
+                .unwrap_err()
+                .downcast::<ParseError>()
+                .unwrap()
+        };
+        let err = parse("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n\nsoil-to-seed map:\n1 2 3\n");
+        assert_eq!((err.line, err.column, err.token.as_str()), (6, 9, "seed"));
+
+        let err = parse("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n");
+        assert_eq!((err.line, err.column, err.token.as_str()), (3, 9, "soil"));
+        assert_eq!(err.message, "nothing maps soil on towards location");
+    }
+}
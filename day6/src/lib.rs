@@ -0,0 +1,101 @@
+//! day6 advent 20XX
+use aoc_common::{parse, ParseError, Solution};
+use color_eyre::eyre::Result;
+
+/// Boat races.
+#[derive(Debug, Default)]
+pub struct Day6;
+
+/// The race sheet.
+#[derive(Clone, Debug, Default)]
+pub struct Races {
+    /// Time for each race.
+    pub times: Vec<usize>,
+    /// Record distance for each race.
+    pub distances: Vec<usize>,
+    /// Time for part2 where the digits are all one number.
+    pub time: usize,
+    /// Distance for part2 where the digits are all one number.
+    pub distance: usize,
+}
+
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+    type Input = Races;
+    type Output = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let lines = input.lines().collect::<Vec<_>>();
+        let (Some(time_line), Some(distance_line)) = (lines.first(), lines.get(1)) else {
+            return Err(ParseError::new(
+                lines.len() + 1,
+                1,
+                "",
+                "expected Time: and Distance: lines",
+            )
+            .into());
+        };
+        let Some(time_line) = time_line.strip_prefix("Time:") else {
+            return Err(ParseError::line(1, time_line, "expected Time:").into());
+        };
+        let Some(distance_line) = distance_line.strip_prefix("Distance:") else {
+            return Err(ParseError::line(2, distance_line, "expected Distance:").into());
+        };
+
+        let times = time_line
+            .split_whitespace()
+            .map(|f| parse::number(1, lines[0], f))
+            .collect::<Result<Vec<_>, _>>()?;
+        let distances = distance_line
+            .split_whitespace()
+            .map(|f| parse::number(2, lines[1], f))
+            .collect::<Result<Vec<_>, _>>()?;
+        if times.len() != distances.len() {
+            return Err(ParseError::line(2, lines[1], "need a distance for every time").into());
+        }
+
+        // Part2 just ignores the spaces and treats each line as one number.
+        let time = parse::number(
+            1,
+            lines[0],
+            &time_line.split_whitespace().collect::<String>(),
+        )?;
+        let distance = parse::number(
+            2,
+            lines[1],
+            &distance_line.split_whitespace().collect::<String>(),
+        )?;
+        Ok(Races {
+            times,
+            distances,
+            time,
+            distance,
+        })
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<usize> {
+        Ok(input
+            .times
+            .iter()
+            .zip(&input.distances)
+            .map(|(t, d)| ways(*t, *d))
+            .product())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<usize> {
+        Ok(ways(input.time, input.distance))
+    }
+}
+
+// Count the ways to hold the button and still beat the record distance.
+fn ways(time: usize, distance: usize) -> usize {
+    let mut way = 0;
+    // Skip first and last as their distance is always 0
+    for i in 1..time {
+        let dist = (time - i) * i;
+        if dist > distance {
+            way += 1;
+        }
+    }
+    way
+}
@@ -0,0 +1,241 @@
+//! day7 advent 20XX
+use aoc_common::{parse, ParseError, Solution};
+use color_eyre::eyre::Result;
+use std::collections::HashMap;
+use strum_macros::Display;
+use tracing::trace;
+
+/// Camel cards.
+#[derive(Debug, Default)]
+pub struct Day7;
+
+/// A single card. Ordered by strength.
+#[derive(Clone, Debug, Display, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Card {
+    /// A J in part2.
+    Joker,
+    /// 2-9 plus T as 10.
+    Number(u32),
+    /// A J in part1.
+    Jack,
+    /// Q
+    Queen,
+    /// K
+    King,
+    /// A
+    Ace,
+}
+
+/// A hand with the cards in their original order. Ordered by hand type and
+/// then by the cards.
+#[derive(Clone, Debug, Display, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Hand {
+    /// No matches.
+    HighCard(Vec<Card>),
+    /// 2 of one card.
+    OnePair(Vec<Card>),
+    /// 2 of one card and 2 of another.
+    TwoPair(Vec<Card>),
+    /// 3 of one card.
+    ThreeOfAKind(Vec<Card>),
+    /// 3 of one card and 2 of another.
+    FullHouse(Vec<Card>),
+    /// 4 of one card.
+    FourOfAKind(Vec<Card>),
+    /// All the same card.
+    FiveOfAKind(Vec<Card>),
+}
+
+/// Each hand with its bid scored both ways.
+#[derive(Clone, Debug, Default)]
+pub struct Hands {
+    /// Hands with J as a jack.
+    pub part1: Vec<(Hand, usize)>,
+    /// Hands with J as a joker.
+    pub part2: Vec<(Hand, usize)>,
+}
+
+impl Solution for Day7 {
+    const DAY: u32 = 7;
+    type Input = Hands;
+    type Output = usize;
+
+    // Input has the form:
+    //
+    // XXXXX nnn
+    //
+    // Where X are various combinations of cards forming 5 card poker hands
+    // of 5 of a kind, 4 of a kind, full hourse, 2 pair, pair and high card.
+    // i.e. no straights/flushes here.
+    //
+    // The cards don't change order so you have to find the best hand and then
+    // sort them by hand type. Then inside each hand they sort based on Card
+    // which is ordered.
+    //
+    // Once sorted make a sum of <pos> * nnn
+    //
+    // Part2 is the same except there can now be jokers (represented as J) which
+    // make hands as expected (wild card) but for sorting are the worst card.
+    // Compute the same sum once you get a proper sort.
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let mut hands = Hands::default();
+        for (line_num, line) in input.lines().enumerate() {
+            let line_num = line_num + 1;
+            let parts = line.split_whitespace().collect::<Vec<_>>();
+            if parts.len() != 2 || parts[0].len() != 5 {
+                return Err(ParseError::line(line_num, line, "expected 5 cards and a bid").into());
+            }
+            let mut cards = vec![];
+            let mut counts = HashMap::new();
+            let mut cards_part2 = vec![];
+            let mut counts_part2 = HashMap::new();
+            let bid = parse::number(line_num, line, parts[1])?;
+            for (pos, c) in parts[0].char_indices() {
+                let mut card = match c {
+                    '2' => Card::Number(2),
+                    '3' => Card::Number(3),
+                    '4' => Card::Number(4),
+                    '5' => Card::Number(5),
+                    '6' => Card::Number(6),
+                    '7' => Card::Number(7),
+                    '8' => Card::Number(8),
+                    '9' => Card::Number(9),
+                    'T' => Card::Number(10),
+                    'J' => Card::Jack,
+                    'Q' => Card::Queen,
+                    'K' => Card::King,
+                    'A' => Card::Ace,
+                    _ => {
+                        let card = &parts[0][pos..pos + c.len_utf8()];
+                        return Err(ParseError::at(line_num, line, card, "invalid card").into());
+                    }
+                };
+                counts
+                    .entry(card.clone())
+                    .and_modify(|f| *f += 1)
+                    .or_insert(1);
+                cards.push(card.clone());
+
+                // part2
+                if c == 'J' {
+                    card = Card::Joker;
+                }
+                counts_part2
+                    .entry(card.clone())
+                    .and_modify(|f| *f += 1)
+                    .or_insert(1);
+                cards_part2.push(card);
+            }
+
+            // We don't move cards around so use the counts to determine type of hand.
+            hands.part1.push((make_hand(&counts, cards), bid));
+            hands
+                .part2
+                .push((make_hand(&counts_part2, cards_part2), bid));
+        }
+        Ok(hands)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<usize> {
+        Ok(score(&input.part1))
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<usize> {
+        Ok(score(&input.part2))
+    }
+}
+
+// Sort the hands and then sum up <pos> * bid.
+fn score(hands: &[(Hand, usize)]) -> usize {
+    let mut h = hands.to_vec();
+    h.sort();
+
+    for h in &h {
+        trace!("{h:?}");
+    }
+    h.iter()
+        .enumerate()
+        .fold(0, |acc, f| acc + (f.0 + 1) * f.1 .1)
+}
+
+fn make_hand(counts: &HashMap<Card, usize>, cards: Vec<Card>) -> Hand {
+    // part2
+    // Check for jokers on each part first. Otherwise as above.
+    match counts.len() {
+        1 => Hand::FiveOfAKind(cards),
+        2 => {
+            if counts.contains_key(&Card::Joker) {
+                // Any jokers means 1 or 4 which means 5 of a kind.
+                Hand::FiveOfAKind(cards)
+            } else if *counts.values().max().unwrap() == 4 {
+                // Either AAAAx or AAAKK so if one count is 4 we know 4 of a kind.
+                Hand::FourOfAKind(cards)
+            } else {
+                // Otherwise it has to be XXXYY so a full house.
+                Hand::FullHouse(cards)
+            }
+        }
+        3 => {
+            if counts.contains_key(&Card::Joker) {
+                match counts[&Card::Joker] {
+                    2 | 3 => {
+                        // 3 jokers means 4 of a kind
+                        // 2 jokers means 4 of a kind also (JJxxy)
+                        // 1 joker means 4 of a kind also (Jxxxy)
+                        Hand::FourOfAKind(cards)
+                    }
+                    1 => {
+                        // 1 Joker means either xxxJy or xxJyy. The former is 4 of a kind
+                        // and the latter is a full house.
+                        if *counts.values().max().unwrap() == 3 {
+                            Hand::FourOfAKind(cards)
+                        } else {
+                            Hand::FullHouse(cards)
+                        }
+                    }
+                    _ => panic!(),
+                }
+            } else {
+                // Either AAAxy or AAKKy so if one count is 3 we know 3 of a kind.
+                if *counts.values().max().unwrap() == 3 {
+                    Hand::ThreeOfAKind(cards)
+                } else {
+                    Hand::TwoPair(cards)
+                }
+            }
+        }
+        4 => {
+            if counts.contains_key(&Card::Joker) {
+                // any joker means xyzaa == 3 of a kind for a=J or x|y|z=J
+                Hand::ThreeOfAKind(cards)
+            } else {
+                Hand::OnePair(cards)
+            }
+        }
+        5 => {
+            if counts.contains_key(&Card::Joker) {
+                // any joker means xyzab == pair
+                Hand::OnePair(cards)
+            } else {
+                Hand::HighCard(cards)
+            }
+        }
+        _ => panic!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn parse_bad_card() {
+        let err = Day7
+            .parse("32T3K 765\nT55X5 684\n")
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 4, "X"));
+    }
+}
@@ -0,0 +1,253 @@
+//! day8 advent 20XX
+use aoc_common::{ParseError, Solution};
+use color_eyre::eyre::{eyre, Result};
+use std::collections::{HashMap, HashSet};
+
+/// Haunted wasteland.
+#[derive(Debug, Default)]
+pub struct Day8;
+
+/// The left/right instructions and the node network.
+#[derive(Clone, Debug, Default)]
+pub struct Network {
+    /// L/R steps to repeat.
+    pub steps: String,
+    /// Map of node to its (left, right) nodes.
+    pub conditions: HashMap<String, (String, String)>,
+}
+
+impl Solution for Day8 {
+    const DAY: u32 = 8;
+    type Input = Network;
+    type Output = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let lines = input.lines().collect::<Vec<_>>();
+        let Some(steps) = lines.first() else {
+            return Err(ParseError::new(1, 1, "", "empty input").into());
+        };
+        if steps.is_empty() {
+            return Err(ParseError::new(1, 1, "", "no steps to follow").into());
+        }
+        if let Some(pos) = steps.find(|c| c != 'L' && c != 'R') {
+            return Err(ParseError::at(1, steps, &steps[pos..], "steps must be L or R").into());
+        }
+        let mut conditions = HashMap::new();
+        // Every left and right along with where so they can be checked once
+        // all the nodes are known.
+        let mut references = vec![];
+        // Node lines look like AAA = (BBB, CCC)
+        for (line_num, line) in lines.iter().enumerate().skip(2) {
+            let parts = line.split_whitespace().collect::<Vec<_>>();
+            if parts.len() != 4
+                || parts[1] != "="
+                || !parts[2].starts_with('(')
+                || !parts[2].ends_with(',')
+                || !parts[3].ends_with(')')
+            {
+                return Err(
+                    ParseError::line(line_num + 1, line, "expected NODE = (LEFT, RIGHT)").into(),
+                );
+            }
+            let left = parts[2].trim_end_matches(',').trim_start_matches('(');
+            let right = parts[3].trim_end_matches(')');
+            conditions.insert(parts[0].to_string(), (left.to_string(), right.to_string()));
+            references.extend([(line_num + 1, *line, left), (line_num + 1, *line, right)]);
+        }
+        for (line_num, line, node) in references {
+            if !conditions.contains_key(node) {
+                return Err(ParseError::at(line_num, line, node, "unknown node").into());
+            }
+        }
+        Ok(Network {
+            steps: (*steps).to_string(),
+            conditions,
+        })
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<usize> {
+        if !input.conditions.contains_key("AAA") {
+            return Err(eyre!("no AAA node to start from"));
+        }
+        find_total("AAA", &input.conditions, &input.steps)
+    }
+
+    // For part2 we need to find everything ending with "A" as a start position
+    // and compute their path. Then the way to get them all together is to get the
+    // LCM of all 6 numbers which is the fold at the end.
+    //
+    // It's not guarenteed LCM works here except if you check each path you'll
+    // see it takes N steps to get to a Z and then the same N to return to that
+    // Z. At that point LCM is valid for "when do all 6 paths meet at the same time?".
+    // If LCM didn't work likely Chinese Remainder Therom could be used.
+    //
+    // So ghost() checks it does hold for each path rather than give a wrong
+    // answer.
+    fn part2(&self, input: &Self::Input) -> Result<usize> {
+        let starts = input
+            .conditions
+            .keys()
+            .filter(|f| f.ends_with('A'))
+            .collect::<Vec<_>>();
+        if starts.is_empty() {
+            return Err(eyre!("no nodes ending in A to start from"));
+        }
+        let alone = starts.len() == 1;
+        starts
+            .into_iter()
+            .map(|f| ghost(f, input, alone))
+            .try_fold(1, |acc, n| Ok(num::integer::lcm(acc, n?)))
+    }
+}
+
+/// Slow reference for both parts which walks from every one of `starts` in
+/// lock step until they're all on a node where `end` holds at once.
+///
+/// Part 1 is `simulate(input, &["AAA"], |n| n == "ZZZ", limit)` and part 2
+/// starts from every node ending in A and stops when they all end in Z.
+/// Unlike the LCM in part 2 this makes no assumptions about how the paths
+/// loop. Returns `None` if it takes more than `limit` steps or a start or
+/// step leads to a node which doesn't exist.
+#[must_use]
+pub fn simulate(
+    input: &Network,
+    starts: &[&str],
+    end: impl Fn(&str) -> bool,
+    limit: usize,
+) -> Option<usize> {
+    let mut cur = starts.to_vec();
+    for (n, step) in input.steps.bytes().cycle().take(limit + 1).enumerate() {
+        if !cur.is_empty() && cur.iter().all(|c| end(c)) {
+            return Some(n);
+        }
+        for c in &mut cur {
+            let (left, right) = input.conditions.get(*c)?;
+            *c = if step == b'L' {
+                left.as_str()
+            } else {
+                right.as_str()
+            };
+        }
+    }
+    None
+}
+
+// Follow the steps from `start` until reaching ZZZ and return how many steps
+// it took.
+fn find_total(
+    start: &str,
+    conditions: &HashMap<String, (String, String)>,
+    steps: &str,
+) -> Result<usize> {
+    let mut cur = start;
+    // Where the walk has been at each point in the steps. Being back at one
+    // means it's going round in circles without ever finding ZZZ.
+    let mut seen = HashSet::new();
+    for (tot, (i, s)) in steps.bytes().enumerate().cycle().enumerate() {
+        if !seen.insert((cur, i)) {
+            return Err(eyre!("{start} never reaches ZZZ"));
+        }
+        let (left, right) = conditions.get(cur).ok_or_else(|| eyre!("no node {cur}"))?;
+        cur = if s == b'L' { left } else { right };
+        if cur == "ZZZ" {
+            return Ok(tot + 1);
+        }
+    }
+    Err(eyre!("no steps to follow"))
+}
+
+// How many steps the ghost starting at `start` takes to reach a node ending
+// in Z. It walks until it's back on a node at the same point in the steps,
+// after which everything repeats, to make sure it's only ever on a Z at
+// multiples of that. Then the LCM over every ghost is when they all are.
+// A ghost on its own is done at its first Z whatever it does after.
+fn ghost(start: &str, input: &Network, alone: bool) -> Result<usize> {
+    let mut cur = start;
+    // When the walk was at each node and point in the steps.
+    let mut seen = HashMap::new();
+    // Every time it's been on a Z.
+    let mut zs = vec![];
+    for (tot, (i, s)) in input.steps.bytes().enumerate().cycle().enumerate() {
+        if let Some(looped) = seen.insert((cur, i), tot) {
+            let Some(&first) = zs.first() else {
+                return Err(eyre!("{start} never reaches a node ending in Z"));
+            };
+            // Multiples of first up to here, and the repeating goes round in
+            // a multiple of it too so that carries on forever. Unless it goes
+            // back round to the start which isn't a Z.
+            let regular = looped > 0
+                && (tot - looped) % first == 0
+                && zs.len() == (tot - 1) / first
+                && zs.iter().enumerate().all(|(k, z)| *z == (k + 1) * first);
+            if !alone && !regular {
+                return Err(eyre!(
+                    "{start} isn't on a Z every {first} steps so LCM can't be used"
+                ));
+            }
+            return Ok(first);
+        }
+        if cur.ends_with('Z') {
+            zs.push(tot);
+        }
+        let (left, right) = input
+            .conditions
+            .get(cur)
+            .ok_or_else(|| eyre!("no node {cur}"))?;
+        cur = if s == b'L' { left } else { right };
+    }
+    Err(eyre!("no steps to follow"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_errors() {
+        let parse = |text| {
+            Day8.parse(text)
+                .unwrap_err()
+                .downcast::<ParseError>()
+                .unwrap()
+        };
+        let err = parse("LR\n\nAAA = (BBB, CCC)\n");
+        assert_eq!((err.line, err.column, err.token.as_str()), (3, 8, "BBB"));
+        let err = parse("\n\nAAA = (AAA, AAA)\n");
+        assert_eq!((err.line, err.column, err.token.as_str()), (1, 1, ""));
+    }
+
+    #[test]
+    fn never_reaches_z() -> Result<()> {
+        let day = Day8;
+        let input = day.parse("L\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n")?;
+        let err = day.part1(&input).unwrap_err();
+        assert_eq!(err.to_string(), "AAA never reaches ZZZ");
+        Ok(())
+    }
+
+    // Part 1 walks past other Z nodes to ZZZ.
+    #[test]
+    fn only_zzz_ends_part1() -> Result<()> {
+        let day = Day8;
+        let input = day.parse("R\n\nAAA = (AAA, BBZ)\nBBZ = (BBZ, ZZZ)\nZZZ = (ZZZ, ZZZ)\n")?;
+        assert_eq!(day.part1(&input)?, 2);
+        Ok(())
+    }
+
+    // 11A is on CCZ after 1 step but then takes 3 to get back so LCM doesn't
+    // apply with another ghost around.
+    #[test]
+    fn ghost_out_of_step() -> Result<()> {
+        let day = Day8;
+        let input = day.parse(
+            "L\n\n11A = (CCZ, CCZ)\nCCZ = (DDD, DDD)\nDDD = (EEE, EEE)\nEEE = (CCZ, CCZ)\n\
+             22A = (22Z, 22Z)\n22Z = (22Z, 22Z)\n",
+        )?;
+        let err = day.part2(&input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "11A isn't on a Z every 1 steps so LCM can't be used"
+        );
+        Ok(())
+    }
+}
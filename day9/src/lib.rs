@@ -0,0 +1,96 @@
+//! day9 advent 20XX
+use aoc_common::{parse, ParseError, Solution};
+use color_eyre::eyre::{eyre, Result};
+use tracing::trace;
+
+/// Mirage maintenance.
+#[derive(Debug, Default)]
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u32 = 9;
+    type Input = Vec<Vec<isize>>;
+    type Output = isize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let mut ret = vec![];
+        for (line_num, line) in input.lines().enumerate() {
+            let values = line
+                .split_whitespace()
+                .map(|f| parse::number(line_num + 1, line, f))
+                .collect::<Result<Vec<_>, _>>()?;
+            // Anything less has no differences to extrapolate from.
+            if values.len() < 2 {
+                return Err(
+                    ParseError::line(line_num + 1, line, "expected at least two numbers").into(),
+                );
+            }
+            ret.push(values);
+        }
+        Ok(ret)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<isize> {
+        input.iter().map(|p| Ok(extrapolate(p)?.0)).sum()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<isize> {
+        input.iter().map(|p| Ok(extrapolate(p)?.1)).sum()
+    }
+}
+
+// Keep taking differences until they're all zero and then use those to
+// compute the next value (first in the tuple) and the previous value (second).
+fn extrapolate(line: &[isize]) -> Result<(isize, isize)> {
+    // Seed the initial line.
+    let mut parts = line.to_vec();
+
+    // Keep a record of the last (and first) entry for each line.
+    let mut diffs = vec![];
+    let mut fronts = vec![];
+    loop {
+        trace!("parts: {parts:?}");
+        let (Some(first), Some(last)) = (parts.first(), parts.last()) else {
+            return Err(eyre!("no values to extrapolate from"));
+        };
+        diffs.push(*last);
+        fronts.push(*first);
+        // A single value can't be differenced any further so it's taken to
+        // stay the same.
+        if parts.len() == 1 || parts.iter().all(|f| *f == 0) {
+            break;
+        }
+        parts = parts.windows(2).map(|w| w[1] - w[0]).collect();
+    }
+    trace!("diffs: {diffs:?}");
+    fronts.reverse();
+    let mut last = 0;
+    for i in &fronts {
+        last = *i - last;
+    }
+    Ok((diffs.iter().sum::<isize>(), last))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn too_few_numbers() {
+        let err = Day9
+            .parse("1 2 3\n5\n")
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 1, "5"));
+    }
+
+    // Differences which run out before reaching zero carry on with the
+    // last one.
+    #[test]
+    fn never_zero() -> Result<()> {
+        assert_eq!(extrapolate(&[1, 5])?, (9, -3));
+        assert_eq!(extrapolate(&[1, 3, 7])?, (13, 1));
+        Ok(())
+    }
+}
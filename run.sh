@@ -1,2 +1,2 @@
-cargo b --release
-for i in `seq 1 10`; do echo day$i; cargo r -p day$i -q --release; done
+cargo b --release -p aoc
+cargo r -p aoc -q --release -- run --all "$@"